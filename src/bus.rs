@@ -0,0 +1,221 @@
+/// Everything the CPU talks to through its 16-bit address space.
+///
+/// Implementors decide what lives where: flat RAM, a mirrored region,
+/// a cartridge ROM bank, or a memory-mapped register. The CPU itself
+/// never touches a backing array directly, so new devices can be wired
+/// in without changing CPU code.
+pub trait Bus {
+    fn read(&self, addr: u16) -> u8;
+    fn write(&mut self, addr: u16, data: u8);
+
+    /// Serializes the device's entire backing state into a flat buffer, in
+    /// whatever layout makes `restore` trivial for that implementation.
+    /// Used by [`crate::cpu::CPU::save_state`] to capture the full machine,
+    /// not just the registers the CPU itself tracks.
+    fn snapshot(&self) -> Vec<u8>;
+
+    /// Restores state produced by `snapshot`. `data` must come from a
+    /// snapshot of this same `Bus` implementation with the same backing
+    /// size (e.g. the same `MappedBus` ROM length); callers should not mix
+    /// buffers across `Bus` types.
+    fn restore(&mut self, data: &[u8]);
+
+    fn read_u16(&self, addr: u16) -> u16 {
+        let lo = self.read(addr);
+        let hi = self.read(addr.wrapping_add(1));
+        u16::from_le_bytes([lo, hi])
+    }
+
+    fn write_u16(&mut self, addr: u16, data: u16) {
+        let bytes = data.to_le_bytes();
+        self.write(addr, bytes[0]);
+        self.write(addr.wrapping_add(1), bytes[1]);
+    }
+}
+
+/// A single flat array spanning the full 16-bit address space, matching
+/// the CPU's previous behavior before peripherals existed.
+///
+/// Sized `0x10000` rather than the `0xFFFF` the struct used to carry: the
+/// old size left address `0xFFFF` itself out of bounds, which a plain
+/// `LDA`/`STA` program never touched but the IRQ/BRK vector's high byte
+/// at `0xFFFF` does.
+pub struct FlatMemory {
+    memory: [u8; 0x10000],
+}
+
+impl FlatMemory {
+    pub fn new() -> Self {
+        FlatMemory { memory: [0; 0x10000] }
+    }
+}
+
+impl Default for FlatMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bus for FlatMemory {
+    fn read(&self, addr: u16) -> u8 {
+        self.memory[addr as usize]
+    }
+
+    fn write(&mut self, addr: u16, data: u8) {
+        self.memory[addr as usize] = data;
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        self.memory.to_vec()
+    }
+
+    fn restore(&mut self, data: &[u8]) {
+        self.memory.copy_from_slice(data);
+    }
+}
+
+/// Address ranges recognized by [`MappedBus`].
+const RAM_START: u16 = 0x0000;
+const RAM_END: u16 = 0x1FFF;
+const RAM_MIRROR_MASK: u16 = 0x07FF;
+
+const IO_REGISTERS_START: u16 = 0x2000;
+const IO_REGISTERS_END: u16 = 0x5FFF;
+
+const ROM_START: u16 = 0x8000;
+
+/// A decoded address space with a RAM mirror, a registers window, and a
+/// cartridge ROM region starting at `0x8000`.
+///
+/// This is the shape most 8-bit systems use: a small amount of work RAM
+/// that repeats every `0x800` bytes, a block of memory-mapped registers
+/// for whatever peripherals are attached, and the cartridge mapped in
+/// above it. Future PPU/APU/controller registers can claim addresses in
+/// the registers window without the CPU knowing anything changed.
+pub struct MappedBus {
+    ram: [u8; (RAM_MIRROR_MASK as usize) + 1],
+    registers: [u8; (IO_REGISTERS_END - IO_REGISTERS_START + 1) as usize],
+    rom: Vec<u8>,
+}
+
+impl MappedBus {
+    pub fn new() -> Self {
+        MappedBus {
+            ram: [0; (RAM_MIRROR_MASK as usize) + 1],
+            registers: [0; (IO_REGISTERS_END - IO_REGISTERS_START + 1) as usize],
+            rom: vec![0; 0x10000 - ROM_START as usize],
+        }
+    }
+
+    pub fn load_rom(&mut self, program: &[u8]) {
+        self.rom[..program.len()].copy_from_slice(program);
+    }
+}
+
+impl Default for MappedBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bus for MappedBus {
+    fn read(&self, addr: u16) -> u8 {
+        match addr {
+            RAM_START..=RAM_END => self.ram[(addr & RAM_MIRROR_MASK) as usize],
+            IO_REGISTERS_START..=IO_REGISTERS_END => {
+                self.registers[(addr - IO_REGISTERS_START) as usize]
+            }
+            ROM_START..=0xFFFF => self.rom[(addr - ROM_START) as usize],
+            _ => 0,
+        }
+    }
+
+    fn write(&mut self, addr: u16, data: u8) {
+        match addr {
+            RAM_START..=RAM_END => self.ram[(addr & RAM_MIRROR_MASK) as usize] = data,
+            IO_REGISTERS_START..=IO_REGISTERS_END => {
+                self.registers[(addr - IO_REGISTERS_START) as usize] = data
+            }
+            ROM_START..=0xFFFF => self.rom[(addr - ROM_START) as usize] = data,
+            _ => {}
+        }
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.ram.len() + self.registers.len() + self.rom.len());
+        buf.extend_from_slice(&self.ram);
+        buf.extend_from_slice(&self.registers);
+        buf.extend_from_slice(&self.rom);
+        buf
+    }
+
+    fn restore(&mut self, data: &[u8]) {
+        let (ram, rest) = data.split_at(self.ram.len());
+        let (registers, rom) = rest.split_at(self.registers.len());
+        self.ram.copy_from_slice(ram);
+        self.registers.copy_from_slice(registers);
+        self.rom.copy_from_slice(rom);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn flat_memory_read_write_roundtrip() {
+        let mut bus = FlatMemory::new();
+        bus.write(0x5050, 0xEA);
+        assert_eq!(bus.read(0x5050), 0xEA);
+    }
+
+    #[test]
+    fn mapped_bus_mirrors_ram() {
+        let mut bus = MappedBus::new();
+        bus.write(0x0000, 0x42);
+        assert_eq!(bus.read(0x0800), 0x42);
+        assert_eq!(bus.read(0x1800), 0x42);
+    }
+
+    #[test]
+    fn mapped_bus_rom_region() {
+        let mut bus = MappedBus::new();
+        bus.load_rom(&[0xA9, 0x05, 0x00]);
+        assert_eq!(bus.read(0x8000), 0xA9);
+        assert_eq!(bus.read(0x8001), 0x05);
+    }
+
+    #[test]
+    fn mapped_bus_registers_are_independent_of_ram() {
+        let mut bus = MappedBus::new();
+        bus.write(0x2000, 0x11);
+        assert_eq!(bus.read(0x2000), 0x11);
+        assert_eq!(bus.read(0x0000), 0x00);
+    }
+
+    #[test]
+    fn flat_memory_snapshot_roundtrip() {
+        let mut bus = FlatMemory::new();
+        bus.write(0x1234, 0x56);
+        let snapshot = bus.snapshot();
+
+        let mut restored = FlatMemory::new();
+        restored.restore(&snapshot);
+        assert_eq!(restored.read(0x1234), 0x56);
+    }
+
+    #[test]
+    fn mapped_bus_snapshot_roundtrip() {
+        let mut bus = MappedBus::new();
+        bus.write(0x0000, 0x11);
+        bus.write(0x2000, 0x22);
+        bus.load_rom(&[0xA9, 0x05, 0x00]);
+        let snapshot = bus.snapshot();
+
+        let mut restored = MappedBus::new();
+        restored.restore(&snapshot);
+        assert_eq!(restored.read(0x0000), 0x11);
+        assert_eq!(restored.read(0x2000), 0x22);
+        assert_eq!(restored.read(0x8000), 0xA9);
+    }
+}
@@ -1,16 +1,69 @@
-use crate::opcode::{self, OPCODES_MAP};
+use std::fs;
+use std::io;
+use std::path::Path;
 
-pub struct  CPU {
+use crate::bus::{Bus, FlatMemory};
+use crate::opcode::CpuMnemonic;
+use crate::variant::{Nmos6502, Variant};
+
+/// Status register bits, in their standard 6502 positions.
+pub const FLAG_CARRY: u8 = 0b0000_0001;
+pub const FLAG_ZERO: u8 = 0b0000_0010;
+pub const FLAG_INTERRUPT_DISABLE: u8 = 0b0000_0100;
+pub const FLAG_DECIMAL: u8 = 0b0000_1000;
+pub const FLAG_BREAK: u8 = 0b0001_0000;
+pub const FLAG_UNUSED: u8 = 0b0010_0000;
+pub const FLAG_OVERFLOW: u8 = 0b0100_0000;
+pub const FLAG_NEGATIVE: u8 = 0b1000_0000;
+
+const STACK_BASE: u16 = 0x0100;
+const IRQ_BRK_VECTOR: u16 = 0xFFFE;
+const NMI_VECTOR: u16 = 0xFFFA;
+
+/// Version tag for [`CPU::save_state`]'s binary format. Bump this whenever
+/// the layout changes so `load_state` rejects a snapshot from an
+/// incompatible build instead of silently misreading it.
+const STATE_VERSION: u8 = 1;
+
+/// Returned by [`CPU::step`] when the current [`Variant`] leaves the fetched
+/// opcode undefined, so variant-specific gaps (an unimplemented `ROR`, say)
+/// are observable instead of a `todo!()` panic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalOpcode(pub u8);
+
+impl std::fmt::Display for IllegalOpcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "illegal opcode ${:02X}", self.0)
+    }
+}
+
+impl std::error::Error for IllegalOpcode {}
+
+pub struct CPU {
     pub program_counter: u16,
     pub register_a: u8,
     pub register_x: u8,
     pub register_y: u8,
     pub status: u8,
-    memory: [u8; 0xFFFF]
+    pub stack_pointer: u8,
+    /// Total cycles executed since construction (or the last `reset`),
+    /// accumulated one instruction at a time by `step`.
+    pub cycles: u64,
+    bus: Box<dyn Bus>,
+    /// Which 65xx chip this core emulates: which opcodes decode at all, and
+    /// whether decimal-mode `ADC`/`SBC` are honored.
+    variant: Box<dyn Variant>,
+    /// Set by `get_address` when an `Absolute_X`/`Absolute_Y`/`Indirect_Y`
+    /// effective address crosses a page boundary, and by `branch_if` for a
+    /// taken branch; `step` reads and clears it to add the penalty to the
+    /// instruction's base cycle cost.
+    extra_cycles: u64,
+    /// Set once `BRK` has run its interrupt side effects; `run` stops
+    /// fetching further instructions once this is true.
+    halted: bool,
 }
 
-
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
 pub enum AddressingMode {
    Accumulator,
@@ -30,73 +83,157 @@ pub enum AddressingMode {
 
 impl Default for CPU {
     fn default() -> Self {
-        Self::new()
+        Self::new(Box::new(FlatMemory::new()), Box::new(Nmos6502))
     }
 }
 
 impl CPU {
-    pub fn new() -> Self {
+    /// Builds a CPU around the given bus and chip variant, the way
+    /// `mos6502`'s `CPU::new(Memory::new())` hands the core its address
+    /// space up front.
+    pub fn new(bus: Box<dyn Bus>, variant: Box<dyn Variant>) -> Self {
         CPU {
             register_a: 0,
             register_x: 0,
             register_y: 0,
             status: 0,
             program_counter: 0,
-            memory: [0; 0xFFFF],
+            stack_pointer: 0xFD,
+            cycles: 0,
+            bus,
+            variant,
+            extra_cycles: 0,
+            halted: false,
         }
     }
-        
+
     pub fn reset(&mut self) {
         self.register_a = 0;
         self.register_x = 0;
+        self.register_y = 0;
         self.status = 0;
+        self.stack_pointer = 0xFD;
         self.program_counter = self.mem_read_u16(0xFFFC);
+        self.cycles = 0;
+        self.halted = false;
+    }
+
+    fn get_flag(&self, flag: u8) -> bool {
+        self.status & flag != 0
+    }
+
+    fn set_flag(&mut self, flag: u8, value: bool) {
+        if value { self.status |= flag; } else { self.status &= !flag; }
     }
 
     fn set_flags(&mut self, operation_result: u8) {
-        match operation_result {
-            0 => { self.status |= 0b0000_0010; },
-            _ => { self.status &= 0b1111_1101; }
-        }
+        self.set_flag(FLAG_ZERO, operation_result == 0);
+        self.set_flag(FLAG_NEGATIVE, operation_result & FLAG_NEGATIVE != 0);
+    }
 
-        if operation_result & 0b1000_0000  != 0 {
-            self.status |= 0b1000_0000;
+    /// Whether `ADC`/`SBC` should run their BCD path: the `D` flag is set
+    /// *and* the current variant's silicon actually implements decimal
+    /// mode (the NES's Ricoh 2A03 has it wired out, for instance).
+    fn decimal_mode_active(&self) -> bool {
+        self.get_flag(FLAG_DECIMAL) && self.variant.supports_decimal_mode()
+    }
+
+    fn push(&mut self, value: u8) {
+        self.mem_write(STACK_BASE + self.stack_pointer as u16, value);
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+    }
+
+    fn pull(&mut self) -> u8 {
+        self.stack_pointer = self.stack_pointer.wrapping_add(1);
+        self.mem_read(STACK_BASE + self.stack_pointer as u16)
+    }
+
+    fn push_u16(&mut self, value: u16) {
+        let bytes = value.to_le_bytes();
+        self.push(bytes[1]);
+        self.push(bytes[0]);
+    }
+
+    fn pull_u16(&mut self) -> u16 {
+        let lo = self.pull();
+        let hi = self.pull();
+        u16::from_le_bytes([lo, hi])
+    }
+
+    /// Pushes the return address and status, sets the interrupt-disable
+    /// flag, and jumps through `vector`. Shared by `BRK`, `irq`, and `nmi`;
+    /// callers decide what's already in `program_counter` and whether the
+    /// pushed status carries the B flag.
+    fn interrupt(&mut self, vector: u16, status_with_break: bool) {
+        self.push_u16(self.program_counter);
+        let mut pushed_status = self.status | FLAG_UNUSED;
+        if status_with_break {
+            pushed_status |= FLAG_BREAK;
+        } else {
+            pushed_status &= !FLAG_BREAK;
         }
-        else {
-            self.status &= 0b0111_1111;
+        self.push(pushed_status);
+        self.set_flag(FLAG_INTERRUPT_DISABLE, true);
+        self.program_counter = self.mem_read_u16(vector);
+    }
+
+    /// Maskable interrupt request; ignored while `FLAG_INTERRUPT_DISABLE` is set.
+    pub fn irq(&mut self) {
+        if !self.get_flag(FLAG_INTERRUPT_DISABLE) {
+            self.interrupt(IRQ_BRK_VECTOR, false);
         }
     }
 
+    /// Non-maskable interrupt; always taken.
+    pub fn nmi(&mut self) {
+        self.interrupt(NMI_VECTOR, false);
+    }
+
     fn mem_read(&self, addr: u16) -> u8 {
-        self.memory[addr as usize]
+        self.bus.read(addr)
     }
 
     fn mem_write(&mut self, addr: u16, data: u8) {
-        self.memory[addr as usize] = data;
+        self.bus.write(addr, data);
     }
 
     fn mem_read_u16(&self, addr: u16) -> u16 {
-        let lo = self.mem_read(addr);
-        let hi = self.mem_read(addr+1);
-        u16::from_le_bytes([lo, hi])
+        self.bus.read_u16(addr)
     }
 
     fn mem_write_u16(&mut self, addr: u16, data: u16) {
-        let data = data.to_le_bytes();
-        self.mem_write(addr, data[0]);
-        self.mem_write(addr+1, data[1]);
+        self.bus.write_u16(addr, data);
     }
 
     pub fn load(&mut self, program: Vec<u8>) {
-        self.memory[0x8000 .. (0x8000 + program.len())]
-            .copy_from_slice(&program[..]);
+        for (offset, byte) in program.iter().enumerate() {
+            self.mem_write(0x8000 + offset as u16, *byte);
+        }
         self.mem_write_u16(0xFFFC, 0x8000);
     }
 
-    fn get_address(&mut self, addressing_mode: &AddressingMode) -> Option<u16> {
+    /// Adds the 1-cycle page-crossing penalty to `extra_cycles` if `base`
+    /// and `effective` fall in different 256-byte pages.
+    fn note_page_cross(&mut self, base: u16, effective: u16) {
+        if base & 0xFF00 != effective & 0xFF00 {
+            self.extra_cycles += 1;
+        }
+    }
+
+    /// Resolves `addressing_mode` to an operand address, advancing the
+    /// program counter past the operand bytes.
+    ///
+    /// `page_cross_penalty` gates the 1-cycle page-crossing surcharge for
+    /// `Absolute_X`/`Absolute_Y`/`Indirect_Y`: real hardware only pays it for
+    /// read instructions (LDA, ADC, CMP, ...), whose opcode table entries are
+    /// annotated "+1 if page crossed". Stores and read-modify-write ops
+    /// (STA, ASL, INC, ...) always take the extra internal cycle regardless
+    /// of crossing, so their table entries are already flat and callers pass
+    /// `false`.
+    fn get_address(&mut self, addressing_mode: &AddressingMode, page_cross_penalty: bool) -> Option<u16> {
         let address: Option<u16>;
         match addressing_mode {
-            AddressingMode::Implied => address = None,
+            AddressingMode::Implied | AddressingMode::Accumulator => address = None,
             AddressingMode::Immediate => {
                 address = Some(self.program_counter);
                 self.program_counter +=1;
@@ -116,23 +253,33 @@ impl CPU {
                 self.program_counter += 1;
             },
             AddressingMode::Relative => {
-                address = Some(self.program_counter.wrapping_add(
-                    self.mem_read(self.program_counter) as u16
-                ));
+                // The displacement is a signed byte relative to the PC
+                // *after* the operand has been consumed, not the raw
+                // unsigned byte added to the operand's own address.
+                let offset = self.mem_read(self.program_counter) as i8;
                 self.program_counter += 1;
+                address = Some(self.program_counter.wrapping_add(offset as i16 as u16));
             },
             AddressingMode::Absolute => {
                 address = Some(self.mem_read_u16(self.program_counter));
                 self.program_counter += 2;
             },
             AddressingMode::Absolute_X => {
-                address = Some(self.mem_read_u16(self.program_counter)
-                    .wrapping_add(self.register_x as u16));
+                let base = self.mem_read_u16(self.program_counter);
+                let addr = base.wrapping_add(self.register_x as u16);
+                if page_cross_penalty {
+                    self.note_page_cross(base, addr);
+                }
+                address = Some(addr);
                 self.program_counter += 2;
             },
             AddressingMode::Absolute_Y => {
-                address = Some(self.mem_read_u16(self.program_counter)
-                    .wrapping_add(self.register_y as u16));
+                let base = self.mem_read_u16(self.program_counter);
+                let addr = base.wrapping_add(self.register_y as u16);
+                if page_cross_penalty {
+                    self.note_page_cross(base, addr);
+                }
+                address = Some(addr);
                 self.program_counter += 2;
             },
             AddressingMode::Indirect => {
@@ -148,70 +295,465 @@ impl CPU {
             },
             AddressingMode::Indirect_Y => {
                 let addr = self.mem_read(self.program_counter);
-                address = Some(self.mem_read_u16(addr as u16).wrapping_add(self.register_y as u16));
+                let base = self.mem_read_u16(addr as u16);
+                let effective = base.wrapping_add(self.register_y as u16);
+                if page_cross_penalty {
+                    self.note_page_cross(base, effective);
+                }
+                address = Some(effective);
                 self.program_counter += 1;
             },
-            _ => todo!("finish"),
         }
         address
     }
 
     fn lda(&mut self, addressing_mode: &AddressingMode) {
-        let addr = self.get_address(addressing_mode).unwrap();
+        let addr = self.get_address(addressing_mode, true).unwrap();
         self.register_a = self.mem_read(addr);
         self.set_flags(self.register_a);
     }
 
     fn ldx(&mut self, addressing_mode: &AddressingMode) {
-        let addr = self.get_address(addressing_mode).unwrap();
+        let addr = self.get_address(addressing_mode, true).unwrap();
         self.register_x = self.mem_read(addr);
         self.set_flags(self.register_x);
     }
 
     fn ldy(&mut self, addressing_mode: &AddressingMode) {
-        let addr = self.get_address(addressing_mode).unwrap();
+        let addr = self.get_address(addressing_mode, true).unwrap();
         self.register_y = self.mem_read(addr);
         self.set_flags(self.register_y);
     }
-    
+
     fn sta(&mut self, addressing_mode: &AddressingMode) {
-        let addr = self.get_address(addressing_mode).unwrap();
+        let addr = self.get_address(addressing_mode, false).unwrap();
         self.mem_write(addr, self.register_a);
         self.set_flags(self.register_a);
     }
 
+    fn stx(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, false).unwrap();
+        self.mem_write(addr, self.register_x);
+    }
+
+    fn sty(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, false).unwrap();
+        self.mem_write(addr, self.register_y);
+    }
+
     fn tax(&mut self) {
         self.register_x = self.register_a;
         self.set_flags(self.register_x);
     }
 
+    fn tay(&mut self) {
+        self.register_y = self.register_a;
+        self.set_flags(self.register_y);
+    }
+
+    fn txa(&mut self) {
+        self.register_a = self.register_x;
+        self.set_flags(self.register_a);
+    }
+
+    fn tya(&mut self) {
+        self.register_a = self.register_y;
+        self.set_flags(self.register_a);
+    }
+
     fn inx(&mut self) {
         self.register_x = self.register_x.wrapping_add(1);
         self.set_flags(self.register_x);
     }
 
-    pub fn run(&mut self) {
-        loop {
-            let code = self.mem_read(self.program_counter);
-            self.program_counter += 1;
-            let opcode = OPCODES_MAP.get(&code);
-
-            match opcode {
-                Some(opcode) => {
-                    use opcode::CpuMnemonic::*;
-                    match &opcode.mnemonic {
-                        LDA => { self.lda(&opcode.addressing) },
-                        LDX => { self.ldx(&opcode.addressing) },
-                        LDY => { self.ldy(&opcode.addressing) },
-                        STA => { self.sta(&opcode.addressing) },
-                        TAX => { self.tax() },
-                        INX => { self.inx() },
-                        BRK => { return; }
-                    }
-                }
-                None => { todo!("Implement more opcodes!"); }
+    fn iny(&mut self) {
+        self.register_y = self.register_y.wrapping_add(1);
+        self.set_flags(self.register_y);
+    }
+
+    fn dex(&mut self) {
+        self.register_x = self.register_x.wrapping_sub(1);
+        self.set_flags(self.register_x);
+    }
+
+    fn dey(&mut self) {
+        self.register_y = self.register_y.wrapping_sub(1);
+        self.set_flags(self.register_y);
+    }
+
+    fn inc(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, false).unwrap();
+        let result = self.mem_read(addr).wrapping_add(1);
+        self.mem_write(addr, result);
+        self.set_flags(result);
+    }
+
+    fn dec(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, false).unwrap();
+        let result = self.mem_read(addr).wrapping_sub(1);
+        self.mem_write(addr, result);
+        self.set_flags(result);
+    }
+
+    fn and(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        self.register_a &= self.mem_read(addr);
+        self.set_flags(self.register_a);
+    }
+
+    fn ora(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        self.register_a |= self.mem_read(addr);
+        self.set_flags(self.register_a);
+    }
+
+    fn eor(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        self.register_a ^= self.mem_read(addr);
+        self.set_flags(self.register_a);
+    }
+
+    fn bit(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        let value = self.mem_read(addr);
+        let result = self.register_a & value;
+
+        self.set_flag(FLAG_ZERO, result == 0);
+        self.set_flag(FLAG_NEGATIVE, value & FLAG_NEGATIVE != 0);
+        self.set_flag(FLAG_OVERFLOW, value & FLAG_OVERFLOW != 0);
+    }
+
+    /// `A = A + operand + C`, honoring the decimal flag the way NMOS
+    /// hardware does. This is the shared core of ADC; SBC reuses it in
+    /// binary mode via a ones-complemented operand, but decimal SBC has
+    /// its own path in `subtract_decimal` since BCD subtraction isn't
+    /// ones-complement BCD addition. Carry/overflow in decimal mode come
+    /// from the nibble-corrected sum; Z and N still reflect the binary
+    /// result, matching real NMOS parts.
+    fn add_with_carry(&mut self, operand: u8) {
+        let carry_in: i16 = if self.get_flag(FLAG_CARRY) { 1 } else { 0 };
+        let a = self.register_a;
+
+        let binary_sum = a as i16 + operand as i16 + carry_in;
+        let binary_result = binary_sum as u8;
+        let overflow = (a ^ binary_result) & (operand ^ binary_result) & 0x80 != 0;
+
+        if self.decimal_mode_active() {
+            let mut low = (a & 0x0F) as i16 + (operand & 0x0F) as i16 + carry_in;
+            if low > 9 {
+                low = ((low + 6) & 0x0F) + 0x10;
             }
+            let mut sum = (a & 0xF0) as i16 + (operand & 0xF0) as i16 + low;
+            if sum > 0x9F {
+                sum += 0x60;
+            }
+
+            self.set_flag(FLAG_CARRY, sum > 0xFF);
+            self.set_flag(FLAG_OVERFLOW, overflow);
+            self.register_a = (sum & 0xFF) as u8;
+            self.set_flags(binary_result);
+        } else {
+            self.set_flag(FLAG_CARRY, binary_sum > 0xFF);
+            self.set_flag(FLAG_OVERFLOW, overflow);
+            self.register_a = binary_result;
+            self.set_flags(binary_result);
+        }
+    }
+
+    /// Decimal-mode `A = A - operand - (1 - C)`. Binary SBC is `add_with_carry`
+    /// with a ones-complemented operand; BCD subtraction needs its own
+    /// nibble-borrow corrections instead. Carry/overflow/Z/N are all derived
+    /// from the binary subtraction, matching real NMOS parts.
+    fn subtract_decimal(&mut self, operand: u8) {
+        let carry_in: i16 = if self.get_flag(FLAG_CARRY) { 1 } else { 0 };
+        let a = self.register_a;
+
+        let binary_diff = a as i16 - operand as i16 - (1 - carry_in);
+        let binary_result = binary_diff as u8;
+        let overflow = (a ^ operand) & (a ^ binary_result) & 0x80 != 0;
+
+        let mut low = (a & 0x0F) as i16 - (operand & 0x0F) as i16 + carry_in - 1;
+        if low < 0 {
+            low = ((low - 6) & 0x0F) - 0x10;
+        }
+        let mut high = (a & 0xF0) as i16 - (operand & 0xF0) as i16 + low;
+        if high < 0 {
+            high -= 0x60;
+        }
+
+        self.set_flag(FLAG_CARRY, binary_diff >= 0);
+        self.set_flag(FLAG_OVERFLOW, overflow);
+        self.register_a = (high & 0xFF) as u8;
+        self.set_flags(binary_result);
+    }
+
+    fn adc(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        let operand = self.mem_read(addr);
+        self.add_with_carry(operand);
+    }
+
+    fn sbc(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        let operand = self.mem_read(addr);
+        if self.decimal_mode_active() {
+            self.subtract_decimal(operand);
+        } else {
+            self.add_with_carry(!operand);
+        }
+    }
+
+    fn compare(&mut self, register: u8, value: u8) {
+        self.set_flag(FLAG_CARRY, register >= value);
+        self.set_flags(register.wrapping_sub(value));
+    }
+
+    fn cmp(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        let value = self.mem_read(addr);
+        self.compare(self.register_a, value);
+    }
+
+    fn cpx(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        let value = self.mem_read(addr);
+        self.compare(self.register_x, value);
+    }
+
+    fn cpy(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, true).unwrap();
+        let value = self.mem_read(addr);
+        self.compare(self.register_y, value);
+    }
+
+    fn asl(&mut self, addressing_mode: &AddressingMode) {
+        if matches!(addressing_mode, AddressingMode::Accumulator) {
+            let old = self.register_a;
+            self.set_flag(FLAG_CARRY, old & 0b1000_0000 != 0);
+            self.register_a = old << 1;
+            self.set_flags(self.register_a);
+        } else {
+            let addr = self.get_address(addressing_mode, false).unwrap();
+            let old = self.mem_read(addr);
+            self.set_flag(FLAG_CARRY, old & 0b1000_0000 != 0);
+            let result = old << 1;
+            self.mem_write(addr, result);
+            self.set_flags(result);
+        }
+    }
+
+    fn lsr(&mut self, addressing_mode: &AddressingMode) {
+        if matches!(addressing_mode, AddressingMode::Accumulator) {
+            let old = self.register_a;
+            self.set_flag(FLAG_CARRY, old & 0b0000_0001 != 0);
+            self.register_a = old >> 1;
+            self.set_flags(self.register_a);
+        } else {
+            let addr = self.get_address(addressing_mode, false).unwrap();
+            let old = self.mem_read(addr);
+            self.set_flag(FLAG_CARRY, old & 0b0000_0001 != 0);
+            let result = old >> 1;
+            self.mem_write(addr, result);
+            self.set_flags(result);
+        }
+    }
+
+    fn rol(&mut self, addressing_mode: &AddressingMode) {
+        let carry_in = if self.get_flag(FLAG_CARRY) { 1 } else { 0 };
+        if matches!(addressing_mode, AddressingMode::Accumulator) {
+            let old = self.register_a;
+            self.set_flag(FLAG_CARRY, old & 0b1000_0000 != 0);
+            self.register_a = (old << 1) | carry_in;
+            self.set_flags(self.register_a);
+        } else {
+            let addr = self.get_address(addressing_mode, false).unwrap();
+            let old = self.mem_read(addr);
+            self.set_flag(FLAG_CARRY, old & 0b1000_0000 != 0);
+            let result = (old << 1) | carry_in;
+            self.mem_write(addr, result);
+            self.set_flags(result);
+        }
+    }
+
+    fn ror(&mut self, addressing_mode: &AddressingMode) {
+        let carry_in = if self.get_flag(FLAG_CARRY) { 0b1000_0000 } else { 0 };
+        if matches!(addressing_mode, AddressingMode::Accumulator) {
+            let old = self.register_a;
+            self.set_flag(FLAG_CARRY, old & 0b0000_0001 != 0);
+            self.register_a = (old >> 1) | carry_in;
+            self.set_flags(self.register_a);
+        } else {
+            let addr = self.get_address(addressing_mode, false).unwrap();
+            let old = self.mem_read(addr);
+            self.set_flag(FLAG_CARRY, old & 0b0000_0001 != 0);
+            let result = (old >> 1) | carry_in;
+            self.mem_write(addr, result);
+            self.set_flags(result);
+        }
+    }
+
+    fn clc(&mut self) { self.set_flag(FLAG_CARRY, false); }
+    fn sec(&mut self) { self.set_flag(FLAG_CARRY, true); }
+    fn cli(&mut self) { self.set_flag(FLAG_INTERRUPT_DISABLE, false); }
+    fn sei(&mut self) { self.set_flag(FLAG_INTERRUPT_DISABLE, true); }
+    fn cld(&mut self) { self.set_flag(FLAG_DECIMAL, false); }
+    fn sed(&mut self) { self.set_flag(FLAG_DECIMAL, true); }
+    fn clv(&mut self) { self.set_flag(FLAG_OVERFLOW, false); }
+
+    fn pha(&mut self) {
+        self.push(self.register_a);
+    }
+
+    fn pla(&mut self) {
+        self.register_a = self.pull();
+        self.set_flags(self.register_a);
+    }
+
+    fn php(&mut self) {
+        self.push(self.status | FLAG_BREAK | FLAG_UNUSED);
+    }
 
+    fn plp(&mut self) {
+        self.status = (self.pull() & !FLAG_BREAK) | FLAG_UNUSED;
+    }
+
+    fn tsx(&mut self) {
+        self.register_x = self.stack_pointer;
+        self.set_flags(self.register_x);
+    }
+
+    fn txs(&mut self) {
+        self.stack_pointer = self.register_x;
+    }
+
+    fn jsr(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, false).unwrap();
+        self.push_u16(self.program_counter.wrapping_sub(1));
+        self.program_counter = addr;
+    }
+
+    fn rts(&mut self) {
+        self.program_counter = self.pull_u16().wrapping_add(1);
+    }
+
+    fn rti(&mut self) {
+        self.status = (self.pull() & !FLAG_BREAK) | FLAG_UNUSED;
+        self.program_counter = self.pull_u16();
+    }
+
+    /// `BRK` behaves like a hardware interrupt taken from software: it skips
+    /// a padding byte, then pushes return address and status (with the B
+    /// flag set so a handler can tell it apart from a real IRQ) before
+    /// jumping through the IRQ/BRK vector.
+    fn brk(&mut self) {
+        self.program_counter = self.program_counter.wrapping_add(1);
+        self.interrupt(IRQ_BRK_VECTOR, true);
+    }
+
+    fn branch_if(&mut self, condition: bool) {
+        let addr = self.get_address(&AddressingMode::Relative, false).unwrap();
+        if condition {
+            self.extra_cycles += 1;
+            self.note_page_cross(self.program_counter, addr);
+            self.program_counter = addr;
+        }
+    }
+
+    fn jmp(&mut self, addressing_mode: &AddressingMode) {
+        let addr = self.get_address(addressing_mode, false).unwrap();
+        self.program_counter = addr;
+    }
+
+    /// Executes exactly one instruction and returns the cycles it took,
+    /// including the opcode table's base cost plus any page-crossing or
+    /// taken-branch penalties. Page-crossing penalties only apply to the
+    /// read opcodes whose table entry is annotated "+1 if page crossed";
+    /// stores and read-modify-write ops already carry their fixed cost.
+    /// Lets callers interleave CPU execution with timed subsystems (video,
+    /// audio) once those exist. Returned as `u64` rather than a narrower
+    /// type to match `cycles`, the running total it accumulates into.
+    ///
+    /// Returns [`IllegalOpcode`] if the current [`Variant`] leaves the
+    /// fetched byte undefined, instead of panicking, so variant-specific
+    /// gaps are observable.
+    pub fn step(&mut self) -> Result<u64, IllegalOpcode> {
+        self.extra_cycles = 0;
+        let code = self.mem_read(self.program_counter);
+        self.program_counter += 1;
+        let (mnemonic, addressing, base_cycles) = self
+            .variant
+            .decode(code)
+            .ok_or(IllegalOpcode(code))?;
+
+        use CpuMnemonic::*;
+        match mnemonic {
+            LDA => { self.lda(&addressing) },
+            LDX => { self.ldx(&addressing) },
+            LDY => { self.ldy(&addressing) },
+            STA => { self.sta(&addressing) },
+            STX => { self.stx(&addressing) },
+            STY => { self.sty(&addressing) },
+            TAX => { self.tax() },
+            TAY => { self.tay() },
+            TXA => { self.txa() },
+            TYA => { self.tya() },
+            AND => { self.and(&addressing) },
+            ORA => { self.ora(&addressing) },
+            EOR => { self.eor(&addressing) },
+            BIT => { self.bit(&addressing) },
+            ADC => { self.adc(&addressing) },
+            SBC => { self.sbc(&addressing) },
+            CMP => { self.cmp(&addressing) },
+            CPX => { self.cpx(&addressing) },
+            CPY => { self.cpy(&addressing) },
+            INC => { self.inc(&addressing) },
+            DEC => { self.dec(&addressing) },
+            INX => { self.inx() },
+            INY => { self.iny() },
+            DEX => { self.dex() },
+            DEY => { self.dey() },
+            ASL => { self.asl(&addressing) },
+            LSR => { self.lsr(&addressing) },
+            ROL => { self.rol(&addressing) },
+            ROR => { self.ror(&addressing) },
+            CLC => { self.clc() },
+            SEC => { self.sec() },
+            CLI => { self.cli() },
+            SEI => { self.sei() },
+            CLD => { self.cld() },
+            SED => { self.sed() },
+            CLV => { self.clv() },
+            BCC => { self.branch_if(!self.get_flag(FLAG_CARRY)) },
+            BCS => { self.branch_if(self.get_flag(FLAG_CARRY)) },
+            BEQ => { self.branch_if(self.get_flag(FLAG_ZERO)) },
+            BNE => { self.branch_if(!self.get_flag(FLAG_ZERO)) },
+            BMI => { self.branch_if(self.get_flag(FLAG_NEGATIVE)) },
+            BPL => { self.branch_if(!self.get_flag(FLAG_NEGATIVE)) },
+            BVC => { self.branch_if(!self.get_flag(FLAG_OVERFLOW)) },
+            BVS => { self.branch_if(self.get_flag(FLAG_OVERFLOW)) },
+            JMP => { self.jmp(&addressing) },
+            JSR => { self.jsr(&addressing) },
+            RTS => { self.rts() },
+            RTI => { self.rti() },
+            PHA => { self.pha() },
+            PLA => { self.pla() },
+            PHP => { self.php() },
+            PLP => { self.plp() },
+            TSX => { self.tsx() },
+            TXS => { self.txs() },
+            NOP => {},
+            BRK => { self.brk(); self.halted = true; }
+        }
+
+        let result = base_cycles as u64 + self.extra_cycles;
+        self.cycles += result;
+        Ok(result)
+    }
+
+    pub fn run(&mut self) {
+        self.halted = false;
+        while !self.halted {
+            self.step().expect("illegal opcode");
         }
     }
 
@@ -221,17 +763,121 @@ impl CPU {
         self.run();
     }
 
+    /// Captures the entire machine — registers, program counter, stack
+    /// pointer, cycle counter, and the full bus — into a versioned binary
+    /// blob. Safe to call between `step()` calls; mid-instruction state
+    /// never escapes into a snapshot since `step` always runs an
+    /// instruction to completion.
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut buf = vec![
+            STATE_VERSION,
+            self.register_a,
+            self.register_x,
+            self.register_y,
+            self.status,
+            self.stack_pointer,
+        ];
+        buf.extend_from_slice(&self.program_counter.to_le_bytes());
+        buf.extend_from_slice(&self.cycles.to_le_bytes());
+
+        let bus_snapshot = self.bus.snapshot();
+        buf.extend_from_slice(&(bus_snapshot.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&bus_snapshot);
+        buf
+    }
+
+    /// Restores a blob produced by `save_state` onto this CPU's existing
+    /// bus. The bus must already be constructed with the same backing
+    /// layout (e.g. the same `MappedBus` ROM size) the snapshot was taken
+    /// from, since `Bus::restore` expects a buffer of its own snapshot
+    /// length.
+    pub fn load_state(&mut self, data: &[u8]) {
+        assert_eq!(data[0], STATE_VERSION, "unsupported save-state version");
+        self.register_a = data[1];
+        self.register_x = data[2];
+        self.register_y = data[3];
+        self.status = data[4];
+        self.stack_pointer = data[5];
+        self.program_counter = u16::from_le_bytes([data[6], data[7]]);
+        self.cycles = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        let bus_len = u32::from_le_bytes(data[16..20].try_into().unwrap()) as usize;
+        self.bus.restore(&data[20..20 + bus_len]);
+        self.extra_cycles = 0;
+        self.halted = false;
+    }
+
+    /// Writes `save_state`'s blob to `path`, so a front-end can snapshot at
+    /// any instruction boundary and rewind to it later.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.save_state())
+    }
+
+    /// Reads a blob written by `save_to_file` and restores it onto this CPU.
+    pub fn load_from_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let data = fs::read(path)?;
+        self.load_state(&data);
+        Ok(())
+    }
+
 }
 
 #[cfg(test)]
 mod test {
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::vec;
 
 use super::*;
- 
+use crate::bus::FlatMemory;
+
+fn new_cpu() -> CPU {
+    CPU::new(Box::new(FlatMemory::new()), Box::new(Nmos6502))
+}
+
+/// Shared record of `("read" | "write", addr)` accesses, handed out by
+/// `TracingBus::new` so a test can inspect it after the bus has been moved
+/// into a `CPU`.
+type AccessLog = Rc<RefCell<Vec<(&'static str, u16)>>>;
+
+/// Wraps `FlatMemory` and records every access, so tests can assert that
+/// read-modify-write opcodes issue a distinct read then a distinct write
+/// (as real hardware does) instead of mutating the backing store in place.
+struct TracingBus {
+    inner: FlatMemory,
+    log: AccessLog,
+}
+
+impl TracingBus {
+    fn new() -> (Self, AccessLog) {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        (TracingBus { inner: FlatMemory::new(), log: log.clone() }, log)
+    }
+}
+
+impl Bus for TracingBus {
+    fn read(&self, addr: u16) -> u8 {
+        self.log.borrow_mut().push(("read", addr));
+        self.inner.read(addr)
+    }
+
+    fn write(&mut self, addr: u16, data: u8) {
+        self.log.borrow_mut().push(("write", addr));
+        self.inner.write(addr, data);
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        self.inner.snapshot()
+    }
+
+    fn restore(&mut self, data: &[u8]) {
+        self.inner.restore(data);
+    }
+}
+
     #[test]
     fn test_0xa9_lda_immediate_load_data() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa9, 0x05, 0x00]);
         assert_eq!(cpu.register_a, 0x05);
         assert!(cpu.status & 0b0000_0010 == 0b00);
@@ -240,21 +886,21 @@ use super::*;
 
     #[test]
     fn test_0xa9_lda_zero_flag() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa9, 0x00, 0x00]);
         assert!(cpu.status & 0b0000_0010 == 0b10);
     }
 
     #[test]
     fn test_0xa9_lda_negative_flag() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa9, 0xff, 0x00]);
         assert!(cpu.status & 0b1000_0000 != 0);
     }
 
     #[test]
     fn test_0xaa_tax_copy_a_to_x() {
-        let mut cpu: CPU = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa9, 0x50, 0xaa, 0x00]);
         assert_eq!(cpu.register_x, 0x50);
         assert!(cpu.status & 0b0000_0010 == 0b00);
@@ -263,7 +909,7 @@ use super::*;
 
     #[test]
     fn test_0xaa_tax_zero_flag() {
-        let mut cpu: CPU = CPU::new();
+        let mut cpu = new_cpu();
         cpu.register_a = 0x00;
         cpu.load_and_run(vec![0xaa, 0x00]);
         assert!(cpu.status & 0b0000_0010 == 0b10);
@@ -271,14 +917,14 @@ use super::*;
 
     #[test]
     fn test_0xaa_tax_negative_flag() {
-        let mut cpu: CPU = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa9, 0xff, 0xaa, 0x00]);
         assert!(cpu.status & 0b1000_0000 != 0);
     }
 
     #[test]
     fn test_0xe8_inx_increment_x() {
-        let mut cpu: CPU = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xe8, 0x00]);
         assert_eq!(cpu.register_x, 0x01);
         assert!(cpu.status & 0b0000_0010 == 0b00);
@@ -287,7 +933,7 @@ use super::*;
 
     #[test]
     fn test_0xe8_inx_zero_flag() {
-        let mut cpu: CPU = CPU::new();
+        let mut cpu = new_cpu();
 
         /* LDA 0xff -> TAX -> INX */
         cpu.load_and_run(vec![0xa9, 0xff, 0xaa, 0xe8, 0x00]);
@@ -297,7 +943,7 @@ use super::*;
 
     #[test]
     fn test_0xe8_inx_wrap_around() {
-        let mut cpu: CPU = CPU::new();
+        let mut cpu = new_cpu();
 
         /* LDA 0xff -> TAX -> INX -> INX */
         cpu.load_and_run(vec![0xa9, 0xff, 0xaa, 0xe8, 0xe8, 0x00]);
@@ -308,7 +954,7 @@ use super::*;
 
     #[test]
     fn test_0xe8_inx_negative_flag() {
-        let mut cpu: CPU = CPU::new();
+        let mut cpu = new_cpu();
 
         /* LDA 0xfe -> TAX -> INX */
         cpu.load_and_run(vec![0xa9, 0xfe, 0xaa, 0xe8, 0x00]);
@@ -318,7 +964,7 @@ use super::*;
 
     #[test]
     fn test_5_ops_working_together() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa9, 0xc0, 0xaa, 0xe8, 0x00]);
         assert_eq!(cpu.register_x, 0xc1)
     }
@@ -326,14 +972,14 @@ use super::*;
 
     #[test]
     fn test_mem_write_read() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write(0x5050, 0xEA);
         assert_eq!(0xEA, cpu.mem_read(0x5050));
     }
 
     #[test]
     fn test_load() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load(vec![0xa9, 0xa9, 0x50, 0x00]);
         assert_eq!(0xa9, cpu.mem_read(0x8000));
         assert_eq!(0xa9, cpu.mem_read(0x8001));
@@ -343,28 +989,28 @@ use super::*;
 
     #[test]
     fn test_u16_read_write() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write_u16(0xdead, 0xbeef);
         assert_eq!(0xbeef, cpu.mem_read_u16(0xdead));
     }
 
     #[test]
     fn test_0xa2_ldx_immediate() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa2, 0xab, 0x00]);
         assert_eq!(cpu.register_x, 0xab);
     }
 
     #[test]
     fn test_0xa0_ldy_immediate() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.load_and_run(vec![0xa0, 0xab, 0x00]);
         assert_eq!(cpu.register_y, 0xab);
     }
 
     #[test]
     fn test_0xa5_lda_zero_page() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write(0x50, 0xde);
         cpu.load_and_run(vec![0xa5, 0x50, 0x00]);
         assert_eq!(cpu.register_a, 0xde);
@@ -372,7 +1018,7 @@ use super::*;
 
     #[test]
     fn test_0xb5_lda_zero_page_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write(0x51, 0xde);
         /* INX -> LDA 0x50 */
         cpu.load_and_run(vec![0xe8, 0xb5, 0x50, 0x00]);
@@ -381,7 +1027,7 @@ use super::*;
 
     #[test]
     fn test_0xb5_lda_zero_page_x_wrap_around() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write(0x00, 0xde);
         /* INX -> LDA 0xff */
         cpu.load_and_run(vec![0xe8, 0xb5, 0xff, 0x00]);
@@ -390,7 +1036,7 @@ use super::*;
 
     #[test]
     fn test_0xad_lda_absolute() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write(0xdead, 0xbe);
         /* LDA 0xdead */
         cpu.load_and_run(vec![0xad, 0xad, 0xde, 0x00]);
@@ -399,7 +1045,7 @@ use super::*;
 
     #[test]
     fn test_0xbd_lda_absolute_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write(0xdeae, 0xbe);
         /* INX -> LDA 0xdead */
         cpu.load_and_run(vec![0xe8, 0xbd, 0xad, 0xde, 0x00]);
@@ -408,7 +1054,7 @@ use super::*;
 
     #[test]
     fn test_0xb9_lda_absolute_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         cpu.mem_write(0x5050, 0xbe);
         /* LDY 0x50 -> LDA 0x5000 */
         cpu.load_and_run(vec![0xa0, 0x50, 0xb9, 0x00, 0x50, 0x00]);
@@ -417,7 +1063,7 @@ use super::*;
 
     #[test]
     fn test_0xa1_lda_indirect_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* save address of the expected value to 0x0055 */
         cpu.mem_write_u16(0x0055, 0xdead);
         /* save expected value to 0xdead */
@@ -429,7 +1075,7 @@ use super::*;
 
     #[test]
     fn test_0xb1_lda_indirect_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* save address of the expected value to 0x0050 */
         cpu.mem_write_u16(0x0050, 0x5000);
         /* save expected value to 0x5005 */
@@ -441,7 +1087,7 @@ use super::*;
 
     #[test]
     fn test_0xa5_sta_zero_page() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* LDA 0x50 -> STA 0x30 */
         cpu.load_and_run(vec![0xa9, 0x50, 0x85, 0x30, 0x00]);
         assert_eq!(0x50, cpu.mem_read(0x0030));
@@ -449,7 +1095,7 @@ use super::*;
 
     #[test]
     fn test_0x95_sta_zero_page_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* LDA 0xfe -> INX -> STA 0x50 */
         cpu.load_and_run(vec![0xa9, 0xfe, 0xe8, 0x95, 0x50, 0x00]);
         assert_eq!(cpu.mem_read(0x51), 0xfe);
@@ -457,7 +1103,7 @@ use super::*;
 
     #[test]
     fn test_0x8d_sta_absolute() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* LDA 0xbe -> STA 0xdead */
         cpu.load_and_run(vec![0xa9, 0xbe, 0x8d, 0xad, 0xde, 0x00]);
         assert_eq!(cpu.mem_read(0xdead), 0xbe);
@@ -465,7 +1111,7 @@ use super::*;
 
     #[test]
     fn test_0x9d_sta_absolute_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* LDA 0xbe -> INX-> STA 0xdead */
         cpu.load_and_run(vec![0xa9, 0xbe, 0xe8, 0x9d, 0xad, 0xde, 0x00]);
         assert_eq!(cpu.mem_read(0xdeae), 0xbe);
@@ -473,7 +1119,7 @@ use super::*;
 
     #[test]
     fn test_0x99_sta_absolute_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* LDA 0xbe -> LDY 0x50 -> STA 0x5000 */
         cpu.load_and_run(vec![0xa9, 0xbe, 0xa0, 0x50, 0x99, 0x00, 0x50, 0x00]);
         assert_eq!(cpu.mem_read(0x5050), 0xbe);
@@ -481,7 +1127,7 @@ use super::*;
 
     #[test]
     fn test_0x81_sta_indirect_x() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* save address of the expected value to 0x0055 */
         cpu.mem_write_u16(0x0055, 0xdead);
         /* LDA 0xea -> LDX 0x05 -> STA 0x50 => load LDA to the address stored at 0x0055 */
@@ -491,7 +1137,7 @@ use super::*;
 
     #[test]
     fn test_0x91_sta_indirect_y() {
-        let mut cpu = CPU::new();
+        let mut cpu = new_cpu();
         /* save address of the expected value to 0x0050 */
         cpu.mem_write_u16(0x0050, 0x5000);
         /* LDA 0xea -> LDY 0x05 -> LDA 0x50 => load LDA to the (address stored at 0x50) + 0x05 */
@@ -500,4 +1146,393 @@ use super::*;
         assert_eq!(cpu.mem_read(0x5005), 0xea);
     }
 
+    #[test]
+    fn test_0x29_and_immediate() {
+        let mut cpu = new_cpu();
+        /* LDA 0xff -> AND 0x0f */
+        cpu.load_and_run(vec![0xa9, 0xff, 0x29, 0x0f, 0x00]);
+        assert_eq!(cpu.register_a, 0x0f);
+    }
+
+    #[test]
+    fn test_0x09_ora_immediate() {
+        let mut cpu = new_cpu();
+        /* LDA 0xf0 -> ORA 0x0f */
+        cpu.load_and_run(vec![0xa9, 0xf0, 0x09, 0x0f, 0x00]);
+        assert_eq!(cpu.register_a, 0xff);
+    }
+
+    #[test]
+    fn test_0x49_eor_immediate() {
+        let mut cpu = new_cpu();
+        /* LDA 0xff -> EOR 0x0f */
+        cpu.load_and_run(vec![0xa9, 0xff, 0x49, 0x0f, 0x00]);
+        assert_eq!(cpu.register_a, 0xf0);
+    }
+
+    #[test]
+    fn test_0x24_bit_sets_overflow_and_negative_from_operand() {
+        let mut cpu = new_cpu();
+        cpu.mem_write(0x10, 0b1100_0000);
+        /* LDA 0xff -> BIT 0x10 */
+        cpu.load_and_run(vec![0xa9, 0xff, 0x24, 0x10, 0x00]);
+        assert!(cpu.status & 0b1000_0000 != 0);
+        assert!(cpu.status & 0b0100_0000 != 0);
+        assert!(cpu.status & 0b0000_0010 == 0);
+    }
+
+    #[test]
+    fn test_0x69_adc_sets_carry_on_overflow() {
+        let mut cpu = new_cpu();
+        /* LDA 0xff -> ADC 0x01 */
+        cpu.load_and_run(vec![0xa9, 0xff, 0x69, 0x01, 0x00]);
+        assert_eq!(cpu.register_a, 0x00);
+        assert!(cpu.status & 0b0000_0001 != 0);
+        assert!(cpu.status & 0b0000_0010 != 0);
+    }
+
+    #[test]
+    fn test_0x69_adc_sets_overflow_on_signed_overflow() {
+        let mut cpu = new_cpu();
+        /* LDA 0x7f -> ADC 0x01 */
+        cpu.load_and_run(vec![0xa9, 0x7f, 0x69, 0x01, 0x00]);
+        assert_eq!(cpu.register_a, 0x80);
+        assert!(cpu.status & 0b0100_0000 != 0);
+    }
+
+    #[test]
+    fn test_0xe9_sbc_borrows_without_carry_set() {
+        let mut cpu = new_cpu();
+        /* SEC -> LDA 0x05 -> SBC 0x01 */
+        cpu.load_and_run(vec![0x38, 0xa9, 0x05, 0xe9, 0x01, 0x00]);
+        assert_eq!(cpu.register_a, 0x04);
+        assert!(cpu.status & 0b0000_0001 != 0);
+    }
+
+    #[test]
+    fn test_decimal_adc_carries_into_the_tens_digit() {
+        let mut cpu = new_cpu();
+        /* SED -> LDA 0x58 -> ADC 0x46 (58 + 46 = 104, BCD 0x04 with carry) */
+        cpu.load_and_run(vec![0xf8, 0xa9, 0x58, 0x69, 0x46, 0x00]);
+        assert_eq!(cpu.register_a, 0x04);
+        assert!(cpu.get_flag(FLAG_CARRY));
+    }
+
+    #[test]
+    fn test_decimal_adc_without_carry_stays_in_range() {
+        let mut cpu = new_cpu();
+        /* SED -> LDA 0x12 -> ADC 0x34 (12 + 34 = 46) */
+        cpu.load_and_run(vec![0xf8, 0xa9, 0x12, 0x69, 0x34, 0x00]);
+        assert_eq!(cpu.register_a, 0x46);
+        assert!(!cpu.get_flag(FLAG_CARRY));
+    }
+
+    #[test]
+    fn test_decimal_sbc_borrows_across_the_tens_digit() {
+        let mut cpu = new_cpu();
+        /* SED -> SEC -> LDA 0x12 -> SBC 0x21 (12 - 21 = -09, wraps to 91 with no carry) */
+        cpu.load_and_run(vec![0xf8, 0x38, 0xa9, 0x12, 0xe9, 0x21, 0x00]);
+        assert_eq!(cpu.register_a, 0x91);
+        assert!(!cpu.get_flag(FLAG_CARRY));
+    }
+
+    #[test]
+    fn test_decimal_sbc_without_borrow_stays_in_range() {
+        let mut cpu = new_cpu();
+        /* SED -> SEC -> LDA 0x46 -> SBC 0x12 (46 - 12 = 34) */
+        cpu.load_and_run(vec![0xf8, 0x38, 0xa9, 0x46, 0xe9, 0x12, 0x00]);
+        assert_eq!(cpu.register_a, 0x34);
+        assert!(cpu.get_flag(FLAG_CARRY));
+    }
+
+    #[test]
+    fn test_0xc9_cmp_sets_carry_when_a_greater_or_equal() {
+        let mut cpu = new_cpu();
+        /* LDA 0x05 -> CMP 0x05 */
+        cpu.load_and_run(vec![0xa9, 0x05, 0xc9, 0x05, 0x00]);
+        assert!(cpu.status & 0b0000_0001 != 0);
+        assert!(cpu.status & 0b0000_0010 != 0);
+    }
+
+    #[test]
+    fn test_0xe0_cpx_sets_carry_when_x_greater_or_equal() {
+        let mut cpu = new_cpu();
+        /* LDX 0x05 -> CPX 0x05 */
+        cpu.load_and_run(vec![0xa2, 0x05, 0xe0, 0x05, 0x00]);
+        assert!(cpu.status & 0b0000_0001 != 0);
+        assert!(cpu.status & 0b0000_0010 != 0);
+    }
+
+    #[test]
+    fn test_0xc0_cpy_clears_carry_when_y_less() {
+        let mut cpu = new_cpu();
+        /* LDY 0x05 -> CPY 0x06 */
+        cpu.load_and_run(vec![0xa0, 0x05, 0xc0, 0x06, 0x00]);
+        assert!(cpu.status & 0b0000_0001 == 0);
+    }
+
+    #[test]
+    fn test_0xe6_inc_zero_page() {
+        let mut cpu = new_cpu();
+        cpu.mem_write(0x10, 0x09);
+        cpu.load_and_run(vec![0xe6, 0x10, 0x00]);
+        assert_eq!(cpu.mem_read(0x10), 0x0a);
+    }
+
+    #[test]
+    fn test_0xc6_dec_zero_page() {
+        let mut cpu = new_cpu();
+        cpu.mem_write(0x10, 0x09);
+        cpu.load_and_run(vec![0xc6, 0x10, 0x00]);
+        assert_eq!(cpu.mem_read(0x10), 0x08);
+    }
+
+    #[test]
+    fn test_0x0a_asl_accumulator() {
+        let mut cpu = new_cpu();
+        /* LDA 0x81 -> ASL A */
+        cpu.load_and_run(vec![0xa9, 0x81, 0x0a, 0x00]);
+        assert_eq!(cpu.register_a, 0x02);
+        assert!(cpu.status & 0b0000_0001 != 0);
+    }
+
+    #[test]
+    fn test_0x6a_ror_accumulator_rotates_carry_in() {
+        let mut cpu = new_cpu();
+        /* SEC -> LDA 0x00 -> ROR A */
+        cpu.load_and_run(vec![0x38, 0xa9, 0x00, 0x6a, 0x00]);
+        assert_eq!(cpu.register_a, 0x80);
+    }
+
+    #[test]
+    fn test_0x4a_lsr_accumulator() {
+        let mut cpu = new_cpu();
+        /* LDA 0x03 -> LSR A */
+        cpu.load_and_run(vec![0xa9, 0x03, 0x4a, 0x00]);
+        assert_eq!(cpu.register_a, 0x01);
+        assert!(cpu.status & 0b0000_0001 != 0);
+    }
+
+    #[test]
+    fn test_0x2a_rol_accumulator_rotates_carry_in() {
+        let mut cpu = new_cpu();
+        /* SEC -> LDA 0x00 -> ROL A */
+        cpu.load_and_run(vec![0x38, 0xa9, 0x00, 0x2a, 0x00]);
+        assert_eq!(cpu.register_a, 0x01);
+    }
+
+    #[test]
+    fn test_flag_setter_opcodes() {
+        let mut cpu = new_cpu();
+        /* SEC -> CLC -> SEI -> CLI -> SED -> CLD -> CLV, stepped individually
+         * so the trailing flags aren't disturbed by a final BRK. */
+        cpu.load(vec![0x38, 0x18, 0x78, 0x58, 0xf8, 0xd8, 0xb8]);
+        cpu.reset();
+        for _ in 0..7 {
+            cpu.step().unwrap();
+        }
+        assert!(!cpu.get_flag(FLAG_CARRY));
+        assert!(!cpu.get_flag(FLAG_INTERRUPT_DISABLE));
+        assert!(!cpu.get_flag(FLAG_DECIMAL));
+        assert!(!cpu.get_flag(FLAG_OVERFLOW));
+    }
+
+    #[test]
+    fn test_0x90_bcc_branches_when_carry_clear() {
+        let mut cpu = new_cpu();
+        /* BCC +2 -> LDX 0x01 (skipped) -> LDX 0x02 */
+        cpu.load_and_run(vec![0x90, 0x02, 0xa2, 0x01, 0xa2, 0x02, 0x00]);
+        assert_eq!(cpu.register_x, 0x02);
+    }
+
+    #[test]
+    fn test_0x4c_jmp_absolute() {
+        let mut cpu = new_cpu();
+        /* JMP 0x8005 -> (skips LDX 0x01) -> LDX 0x02 */
+        cpu.load_and_run(vec![0x4c, 0x05, 0x80, 0xa2, 0x01, 0xa2, 0x02, 0x00]);
+        assert_eq!(cpu.register_x, 0x02);
+    }
+
+    #[test]
+    fn test_reset_initializes_stack_pointer() {
+        let mut cpu = new_cpu();
+        cpu.reset();
+        assert_eq!(cpu.stack_pointer, 0xFD);
+    }
+
+    #[test]
+    fn test_0x20_jsr_and_0x60_rts_return_to_caller() {
+        let mut cpu = new_cpu();
+        /* JSR $8006 -> LDX #$01 -> BRK
+         * $8006: LDX #$02 -> RTS */
+        cpu.load_and_run(vec![
+            0x20, 0x06, 0x80,
+            0xa2, 0x01,
+            0x00,
+            0xa2, 0x02,
+            0x60,
+        ]);
+        assert_eq!(cpu.register_x, 0x01);
+    }
+
+    #[test]
+    fn test_0x48_pha_and_0x68_pla_roundtrip() {
+        let mut cpu = new_cpu();
+        /* LDA 0x42 -> PHA -> LDA 0x00 -> PLA */
+        cpu.load_and_run(vec![0xa9, 0x42, 0x48, 0xa9, 0x00, 0x68, 0x00]);
+        assert_eq!(cpu.register_a, 0x42);
+    }
+
+    #[test]
+    fn test_asl_memory_operand_is_a_distinct_read_then_write() {
+        let (bus, log) = TracingBus::new();
+        let mut cpu = CPU::new(Box::new(bus), Box::new(Nmos6502));
+        /* STA seeds $10, then ASL $10 must read it before writing it back */
+        cpu.load_and_run(vec![0xa9, 0x81, 0x85, 0x10, 0x06, 0x10, 0x00]);
+
+        let accesses_to_0x10: Vec<&'static str> = log.borrow().iter()
+            .filter(|(_, addr)| *addr == 0x10)
+            .map(|(kind, _)| *kind)
+            .collect();
+        assert_eq!(accesses_to_0x10, vec!["write", "read", "write"]);
+        assert_eq!(cpu.mem_read(0x10), 0x02);
+    }
+
+    #[test]
+    fn test_asl_accumulator_never_touches_the_bus() {
+        let (bus, _log) = TracingBus::new();
+        let mut cpu = CPU::new(Box::new(bus), Box::new(Nmos6502));
+        cpu.load_and_run(vec![0xa9, 0x81, 0x0a, 0x00]); // LDA #$81 -> ASL A
+
+        assert_eq!(cpu.register_a, 0x02);
+    }
+
+    #[test]
+    fn test_brk_pushes_pc_and_status_through_irq_vector() {
+        let mut cpu = new_cpu();
+        cpu.mem_write_u16(0xFFFE, 0x9000);
+        cpu.load_and_run(vec![0x00]);
+        assert_eq!(cpu.program_counter, 0x9000);
+        assert!(cpu.get_flag(FLAG_INTERRUPT_DISABLE));
+    }
+
+    #[test]
+    fn test_step_returns_base_cycles_for_immediate_addressing() {
+        let mut cpu = new_cpu();
+        cpu.load(vec![0xa9, 0x42, 0x00]); // LDA #$42
+        cpu.reset();
+        let cycles = cpu.step().unwrap();
+        assert_eq!(cycles, 2);
+        assert_eq!(cpu.register_a, 0x42);
+    }
+
+    #[test]
+    fn test_step_adds_a_cycle_when_absolute_x_crosses_a_page() {
+        let mut cpu = new_cpu();
+        cpu.mem_write(0x3100, 0xbe);
+        cpu.load(vec![0xa2, 0x01, 0xbd, 0xff, 0x30, 0x00]); // LDX #$01 ; LDA $30FF,X
+        cpu.reset();
+        cpu.step().unwrap();
+        let cycles = cpu.step().unwrap();
+        assert_eq!(cycles, 5);
+        assert_eq!(cpu.register_a, 0xbe);
+    }
+
+    #[test]
+    fn test_step_does_not_add_a_cycle_when_absolute_x_stays_on_the_page() {
+        let mut cpu = new_cpu();
+        cpu.mem_write(0x3001, 0xbe);
+        cpu.load(vec![0xa2, 0x01, 0xbd, 0x00, 0x30, 0x00]); // LDX #$01 ; LDA $3000,X
+        cpu.reset();
+        cpu.step().unwrap();
+        let cycles = cpu.step().unwrap();
+        assert_eq!(cycles, 4);
+    }
+
+    #[test]
+    fn test_sta_absolute_x_does_not_add_a_cycle_when_it_crosses_a_page() {
+        let mut cpu = new_cpu();
+        cpu.load(vec![0xa2, 0x01, 0x9d, 0xff, 0x30, 0x00]); // LDX #$01 ; STA $30FF,X
+        cpu.reset();
+        cpu.step().unwrap();
+        let cycles = cpu.step().unwrap();
+        assert_eq!(cycles, 5);
+        assert_eq!(cpu.mem_read(0x3100), cpu.register_a);
+    }
+
+    #[test]
+    fn test_asl_absolute_x_does_not_add_a_cycle_when_it_crosses_a_page() {
+        let mut cpu = new_cpu();
+        cpu.load(vec![0xa2, 0x01, 0x1e, 0xff, 0x30, 0x00]); // LDX #$01 ; ASL $30FF,X
+        cpu.reset();
+        cpu.step().unwrap();
+        let cycles = cpu.step().unwrap();
+        assert_eq!(cycles, 7);
+    }
+
+    #[test]
+    fn test_taken_branch_adds_a_cycle_and_a_page_cross_adds_another() {
+        let mut cpu = new_cpu();
+        cpu.load(vec![0xa9, 0x01, 0xd0, 0xf8, 0x00]); // LDA #$01 ; BNE -8 (crosses to $7FFC)
+        cpu.reset();
+        cpu.step().unwrap();
+        let cycles = cpu.step().unwrap();
+        assert_eq!(cycles, 4);
+        assert_eq!(cpu.program_counter, 0x7FFC);
+    }
+
+    #[test]
+    fn test_run_accumulates_total_cycles_across_instructions() {
+        let mut cpu = new_cpu();
+        cpu.load_and_run(vec![0xa9, 0x01, 0x00]); // LDA #$01 (2) ; BRK (7)
+        assert_eq!(cpu.cycles, 9);
+    }
+
+    #[test]
+    fn test_save_state_and_load_state_roundtrip_mid_instruction_boundary() {
+        let mut cpu = new_cpu();
+        cpu.load(vec![0xa9, 0x42, 0x85, 0x10, 0xe8, 0x00]); // LDA #$42 ; STA $10 ; INX
+        cpu.reset();
+        cpu.step().unwrap(); // LDA #$42
+        cpu.step().unwrap(); // STA $10
+
+        let snapshot = cpu.save_state();
+
+        cpu.step().unwrap(); // INX, mutates state that the snapshot should not see
+
+        let mut restored = new_cpu();
+        restored.load_state(&snapshot);
+
+        assert_eq!(restored.register_a, 0x42);
+        assert_eq!(restored.register_x, 0x00);
+        assert_eq!(restored.program_counter, cpu.program_counter.wrapping_sub(1));
+        assert_eq!(restored.cycles, cpu.cycles - 2);
+        assert_eq!(restored.mem_read(0x10), 0x42);
+    }
+
+    #[test]
+    fn test_save_to_file_and_load_from_file_roundtrip() {
+        let mut cpu = new_cpu();
+        cpu.load_and_run(vec![0xa9, 0x37, 0x00]); // LDA #$37 ; BRK
+
+        let path = std::env::temp_dir().join("snes_emu_test_save_state.bin");
+        cpu.save_to_file(&path).unwrap();
+
+        let mut restored = new_cpu();
+        restored.load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.register_a, 0x37);
+        assert_eq!(restored.cycles, cpu.cycles);
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported save-state version")]
+    fn test_load_state_rejects_unknown_version() {
+        let mut cpu = new_cpu();
+        let mut bogus = cpu.save_state();
+        bogus[0] = STATE_VERSION.wrapping_add(1);
+        cpu.load_state(&bogus);
+    }
+
 }
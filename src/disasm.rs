@@ -0,0 +1,158 @@
+use crate::cpu::AddressingMode;
+use crate::variant::Variant;
+
+/// One decoded row: the instruction's address, its raw encoded bytes, and
+/// human-readable 6502 assembly text (e.g. `"LDA #$05"`, `"BEQ $C012"`).
+pub struct DisassembledInstruction {
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    pub text: String,
+}
+
+/// The number of operand bytes `addressing` reads after the opcode byte,
+/// i.e. how far [`crate::cpu::CPU::get_address`] advances the program
+/// counter for this mode.
+fn operand_len(addressing: &AddressingMode) -> usize {
+    use AddressingMode::*;
+    match addressing {
+        Implied | Accumulator => 0,
+        Immediate | ZeroPage | ZeroPage_X | ZeroPage_Y | Relative | Indirect_X | Indirect_Y => 1,
+        Absolute | Absolute_X | Absolute_Y | Indirect => 2,
+    }
+}
+
+/// Decodes `code` starting at `base_address` into a sequence of assembly
+/// rows, one per instruction, using the same `variant` a [`crate::cpu::CPU`]
+/// running this code would decode with — so a disassembly never claims an
+/// opcode the variant actually treats as illegal. A byte `variant` leaves
+/// undefined (or a known one whose operand runs past the end of `code`) is
+/// emitted as a `.byte $xx` pseudo-op and skipped on its own, so embedded
+/// data never desyncs the rest of the stream.
+pub fn disassemble(code: &[u8], base_address: u16, variant: &dyn Variant) -> Vec<DisassembledInstruction> {
+    let mut rows = Vec::new();
+    let mut offset = 0usize;
+
+    while offset < code.len() {
+        let address = base_address.wrapping_add(offset as u16);
+        let hex = code[offset];
+        let decoded = variant
+            .decode(hex)
+            .map(|(mnemonic, addressing, _cycles)| (mnemonic, addressing, 1 + operand_len(&addressing)))
+            .filter(|&(.., len)| offset + len <= code.len());
+
+        match decoded {
+            Some((mnemonic, addressing, len)) => {
+                let bytes = code[offset..offset + len].to_vec();
+                let operand = operand_text(&addressing, &bytes, address);
+                let text = format!("{mnemonic:?} {operand}").trim_end().to_string();
+                rows.push(DisassembledInstruction { address, bytes, text });
+                offset += len;
+            }
+            // Undefined for this variant, or a known opcode whose operand
+            // runs past the end of `code`: render the single byte as data
+            // and move on.
+            None => {
+                rows.push(DisassembledInstruction {
+                    address,
+                    bytes: vec![hex],
+                    text: format!(".byte ${hex:02X}"),
+                });
+                offset += 1;
+            }
+        }
+    }
+
+    rows
+}
+
+/// Renders an instruction's operand bytes (including the opcode byte at
+/// `bytes[0]`) the way the addressing mode reads them. `Relative` resolves
+/// the signed displacement into the absolute target address rather than
+/// printing the raw offset, since that's what a human reads a branch as.
+fn operand_text(mode: &AddressingMode, bytes: &[u8], address: u16) -> String {
+    use AddressingMode::*;
+    match mode {
+        Implied => String::new(),
+        Accumulator => "A".to_string(),
+        Immediate => format!("#${:02X}", bytes[1]),
+        ZeroPage => format!("${:02X}", bytes[1]),
+        ZeroPage_X => format!("${:02X},X", bytes[1]),
+        ZeroPage_Y => format!("${:02X},Y", bytes[1]),
+        Relative => {
+            let offset = bytes[1] as i8;
+            let target = address.wrapping_add(2).wrapping_add(offset as i16 as u16);
+            format!("${target:04X}")
+        }
+        Absolute => format!("${:04X}", u16::from_le_bytes([bytes[1], bytes[2]])),
+        Absolute_X => format!("${:04X},X", u16::from_le_bytes([bytes[1], bytes[2]])),
+        Absolute_Y => format!("${:04X},Y", u16::from_le_bytes([bytes[1], bytes[2]])),
+        Indirect => format!("(${:04X})", u16::from_le_bytes([bytes[1], bytes[2]])),
+        Indirect_X => format!("(${:02X},X)", bytes[1]),
+        Indirect_Y => format!("(${:02X}),Y", bytes[1]),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::variant::{Nmos6502, RevisionA};
+
+    fn text_of(code: &[u8], base_address: u16) -> Vec<String> {
+        disassemble(code, base_address, &Nmos6502).into_iter().map(|row| row.text).collect()
+    }
+
+    #[test]
+    fn disassembles_immediate_and_zero_page() {
+        assert_eq!(text_of(&[0xA9, 0x05], 0x8000), vec!["LDA #$05"]);
+        assert_eq!(text_of(&[0xA5, 0x10], 0x8000), vec!["LDA $10"]);
+    }
+
+    #[test]
+    fn disassembles_absolute_indexed() {
+        assert_eq!(text_of(&[0x9D, 0x00, 0x80], 0x8000), vec!["STA $8000,X"]);
+    }
+
+    #[test]
+    fn disassembles_relative_branch_as_a_resolved_target() {
+        // BEQ +0x10, at $C000: target is $C000 + 2 + $10 = $C012.
+        assert_eq!(text_of(&[0xF0, 0x10], 0xC000), vec!["BEQ $C012"]);
+    }
+
+    #[test]
+    fn disassembles_indirect_jmp() {
+        assert_eq!(text_of(&[0x6C, 0xFC, 0xFF], 0x8000), vec!["JMP ($FFFC)"]);
+    }
+
+    #[test]
+    fn disassembles_implied_and_accumulator_without_a_trailing_space() {
+        assert_eq!(text_of(&[0xE8], 0x8000), vec!["INX"]);
+        assert_eq!(text_of(&[0x0A], 0x8000), vec!["ASL A"]);
+    }
+
+    #[test]
+    fn unknown_byte_becomes_a_byte_pseudo_op_and_resyncs_on_the_next_byte() {
+        assert_eq!(text_of(&[0xFF, 0xA9, 0x05], 0x8000), vec![".byte $FF", "LDA #$05"]);
+    }
+
+    #[test]
+    fn truncated_operand_at_end_of_stream_becomes_a_byte_pseudo_op() {
+        assert_eq!(text_of(&[0xA9], 0x8000), vec![".byte $A9"]);
+    }
+
+    #[test]
+    fn addresses_advance_by_instruction_length() {
+        let rows = disassemble(&[0xA9, 0x05, 0xE8], 0x8000, &Nmos6502);
+        assert_eq!(rows[0].address, 0x8000);
+        assert_eq!(rows[1].address, 0x8002);
+    }
+
+    #[test]
+    fn an_opcode_the_variant_leaves_undefined_becomes_a_byte_pseudo_op() {
+        // $6A is ROR, which RevisionA doesn't decode (see variant::RevisionA).
+        // A RevisionA disassembly must not claim it as an instruction, since
+        // CPU::step() on that variant would reject it as an IllegalOpcode.
+        let rows = disassemble(&[0x6A, 0xE8], 0x8000, &RevisionA);
+        assert_eq!(rows[0].text, ".byte $6A");
+        assert_eq!(rows[1].text, "INX");
+    }
+}
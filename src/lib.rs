@@ -0,0 +1,19 @@
+//! A 6502-family CPU core. Memory access is decoupled behind the [`bus::Bus`]
+//! trait rather than a fixed array owned by the CPU, so callers can plug in
+//! RAM mirrors, ROM regions, and memory-mapped registers (see
+//! [`bus::FlatMemory`] for the flat default and [`bus::MappedBus`] for an
+//! address-decoded example) without touching [`cpu::CPU`] itself. Which
+//! opcodes are legal and whether decimal arithmetic is honored are likewise
+//! decoupled behind [`variant::Variant`], so the same core can emulate
+//! several distinct 65xx chip revisions. [`peripheral::PeripheralBus`] takes
+//! the decoupling further, letting devices claim address ranges over any
+//! backing `Bus` — including bank-switched cartridge windows (see
+//! [`peripheral::BankSwitchedWindow`]) — without the CPU knowing anything
+//! changed.
+
+pub mod bus;
+pub mod cpu;
+pub mod disasm;
+pub mod opcode;
+pub mod peripheral;
+pub mod variant;
@@ -2,28 +2,47 @@ use lazy_static::lazy_static;
 use std::collections::HashMap;
 use crate::cpu::AddressingMode;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum CpuMnemonic {
-   BRK,
-   LDA,
-   LDX,
-   LDY,
-   TAX,
-   INX,
-   STA,
+   // loads / stores
+   LDA, LDX, LDY, STA, STX, STY,
+   // register transfers
+   TAX, TAY, TXA, TYA, TSX, TXS,
+   // logic
+   AND, ORA, EOR, BIT,
+   // arithmetic
+   ADC, SBC, CMP, CPX, CPY,
+   // inc / dec
+   INC, DEC, INX, INY, DEX, DEY,
+   // shifts / rotates
+   ASL, LSR, ROL, ROR,
+   // flag ops
+   CLC, SEC, CLI, SEI, CLD, SED, CLV,
+   // branches
+   BCC, BCS, BEQ, BNE, BMI, BPL, BVC, BVS,
+   // jumps / calls
+   JMP, JSR, RTS,
+   // stack
+   PHA, PLA, PHP, PLP,
+   // system
+   BRK, RTI, NOP,
 }
 
 pub struct Opcode {
    pub hex: u8,
    pub mnemonic: CpuMnemonic,
+   pub len: u8,
    pub cycles: u8,
    pub addressing: AddressingMode,
 }
 
 impl Opcode {
-   fn new(hex: u8, mnemonic: CpuMnemonic, cycles: u8, addressing: AddressingMode) -> Self {
+   fn new(hex: u8, mnemonic: CpuMnemonic, len: u8, cycles: u8, addressing: AddressingMode) -> Self {
       Opcode {
          hex,
          mnemonic,
+         len,
          cycles,
          addressing
       }
@@ -31,41 +50,204 @@ impl Opcode {
 }
 
 use CpuMnemonic::*;
+use AddressingMode::*;
+
 lazy_static! {
+   /// The full legal NMOS 6502 instruction set, keyed by opcode byte.
+   /// `run` fetches a byte, looks the entry up here, resolves the operand
+   /// address once via `get_address`, then dispatches on `mnemonic`.
    pub static ref CPU_OPS_CODES: Vec<Opcode> = vec![
-      Opcode::new(0x00, BRK, 7, AddressingMode::Implied),
-
-      Opcode::new(0xA9, LDA, 2, AddressingMode::Immediate),
-      Opcode::new(0xA5, LDA, 3, AddressingMode::ZeroPage),
-      Opcode::new(0xB5, LDA, 4, AddressingMode::ZeroPage_X),
-      Opcode::new(0xAD, LDA, 4, AddressingMode::Absolute),
-      Opcode::new(0xBD, LDA, 4 /* +1 if page crossed */, AddressingMode::Absolute_X),
-      Opcode::new(0xB9, LDA, 4 /* +1 if page crossed */, AddressingMode::Absolute_Y),
-      Opcode::new(0xA1, LDA, 6, AddressingMode::Indirect_X),
-      Opcode::new(0xB1, LDA, 5 /* +1 if page crossed */, AddressingMode::Indirect_Y),
-
-      Opcode::new(0xA2, LDX, 2, AddressingMode::Immediate),
-      Opcode::new(0xA6, LDX, 3, AddressingMode::ZeroPage),
-      Opcode::new(0xB6, LDX, 4, AddressingMode::ZeroPage_Y),
-      Opcode::new(0xAE, LDX, 4, AddressingMode::Absolute),
-      Opcode::new(0xBE, LDX, 4 /* +1 if page crossed */, AddressingMode::Absolute_Y),
-
-      Opcode::new(0xA0, LDY, 2, AddressingMode::Immediate),
-      Opcode::new(0xA4, LDY, 3, AddressingMode::ZeroPage),
-      Opcode::new(0xB4, LDY, 4, AddressingMode::ZeroPage_X),
-      Opcode::new(0xAC, LDY, 4, AddressingMode::Absolute),
-      Opcode::new(0xBC, LDY, 4 /* +1 if page crossed */, AddressingMode::Absolute_X),
-
-      Opcode::new(0xAA, TAX, 2, AddressingMode::Implied),
-      Opcode::new(0xE8, INX, 2, AddressingMode::Implied),
-
-      Opcode::new(0x85, STA, 3, AddressingMode::ZeroPage),
-      Opcode::new(0x95, STA, 4, AddressingMode::ZeroPage_X),
-      Opcode::new(0x8D, STA, 4, AddressingMode::Absolute),
-      Opcode::new(0x9D, STA, 5, AddressingMode::Absolute_X),
-      Opcode::new(0x99, STA, 5, AddressingMode::Absolute_Y),
-      Opcode::new(0x81, STA, 6, AddressingMode::Indirect_X),
-      Opcode::new(0x91, STA, 6, AddressingMode::Indirect_Y),
+      Opcode::new(0x00, BRK, 1, 7, Implied),
+      Opcode::new(0xEA, NOP, 1, 2, Implied),
+
+      // loads
+      Opcode::new(0xA9, LDA, 2, 2, Immediate),
+      Opcode::new(0xA5, LDA, 2, 3, ZeroPage),
+      Opcode::new(0xB5, LDA, 2, 4, ZeroPage_X),
+      Opcode::new(0xAD, LDA, 3, 4, Absolute),
+      Opcode::new(0xBD, LDA, 3, 4 /* +1 if page crossed */, Absolute_X),
+      Opcode::new(0xB9, LDA, 3, 4 /* +1 if page crossed */, Absolute_Y),
+      Opcode::new(0xA1, LDA, 2, 6, Indirect_X),
+      Opcode::new(0xB1, LDA, 2, 5 /* +1 if page crossed */, Indirect_Y),
+
+      Opcode::new(0xA2, LDX, 2, 2, Immediate),
+      Opcode::new(0xA6, LDX, 2, 3, ZeroPage),
+      Opcode::new(0xB6, LDX, 2, 4, ZeroPage_Y),
+      Opcode::new(0xAE, LDX, 3, 4, Absolute),
+      Opcode::new(0xBE, LDX, 3, 4 /* +1 if page crossed */, Absolute_Y),
+
+      Opcode::new(0xA0, LDY, 2, 2, Immediate),
+      Opcode::new(0xA4, LDY, 2, 3, ZeroPage),
+      Opcode::new(0xB4, LDY, 2, 4, ZeroPage_X),
+      Opcode::new(0xAC, LDY, 3, 4, Absolute),
+      Opcode::new(0xBC, LDY, 3, 4 /* +1 if page crossed */, Absolute_X),
+
+      // stores
+      Opcode::new(0x85, STA, 2, 3, ZeroPage),
+      Opcode::new(0x95, STA, 2, 4, ZeroPage_X),
+      Opcode::new(0x8D, STA, 3, 4, Absolute),
+      Opcode::new(0x9D, STA, 3, 5, Absolute_X),
+      Opcode::new(0x99, STA, 3, 5, Absolute_Y),
+      Opcode::new(0x81, STA, 2, 6, Indirect_X),
+      Opcode::new(0x91, STA, 2, 6, Indirect_Y),
+
+      Opcode::new(0x86, STX, 2, 3, ZeroPage),
+      Opcode::new(0x96, STX, 2, 4, ZeroPage_Y),
+      Opcode::new(0x8E, STX, 3, 4, Absolute),
+
+      Opcode::new(0x84, STY, 2, 3, ZeroPage),
+      Opcode::new(0x94, STY, 2, 4, ZeroPage_X),
+      Opcode::new(0x8C, STY, 3, 4, Absolute),
+
+      // transfers
+      Opcode::new(0xAA, TAX, 1, 2, Implied),
+      Opcode::new(0xA8, TAY, 1, 2, Implied),
+      Opcode::new(0x8A, TXA, 1, 2, Implied),
+      Opcode::new(0x98, TYA, 1, 2, Implied),
+      Opcode::new(0xBA, TSX, 1, 2, Implied),
+      Opcode::new(0x9A, TXS, 1, 2, Implied),
+
+      // logic
+      Opcode::new(0x29, AND, 2, 2, Immediate),
+      Opcode::new(0x25, AND, 2, 3, ZeroPage),
+      Opcode::new(0x35, AND, 2, 4, ZeroPage_X),
+      Opcode::new(0x2D, AND, 3, 4, Absolute),
+      Opcode::new(0x3D, AND, 3, 4 /* +1 if page crossed */, Absolute_X),
+      Opcode::new(0x39, AND, 3, 4 /* +1 if page crossed */, Absolute_Y),
+      Opcode::new(0x21, AND, 2, 6, Indirect_X),
+      Opcode::new(0x31, AND, 2, 5 /* +1 if page crossed */, Indirect_Y),
+
+      Opcode::new(0x09, ORA, 2, 2, Immediate),
+      Opcode::new(0x05, ORA, 2, 3, ZeroPage),
+      Opcode::new(0x15, ORA, 2, 4, ZeroPage_X),
+      Opcode::new(0x0D, ORA, 3, 4, Absolute),
+      Opcode::new(0x1D, ORA, 3, 4 /* +1 if page crossed */, Absolute_X),
+      Opcode::new(0x19, ORA, 3, 4 /* +1 if page crossed */, Absolute_Y),
+      Opcode::new(0x01, ORA, 2, 6, Indirect_X),
+      Opcode::new(0x11, ORA, 2, 5 /* +1 if page crossed */, Indirect_Y),
+
+      Opcode::new(0x49, EOR, 2, 2, Immediate),
+      Opcode::new(0x45, EOR, 2, 3, ZeroPage),
+      Opcode::new(0x55, EOR, 2, 4, ZeroPage_X),
+      Opcode::new(0x4D, EOR, 3, 4, Absolute),
+      Opcode::new(0x5D, EOR, 3, 4 /* +1 if page crossed */, Absolute_X),
+      Opcode::new(0x59, EOR, 3, 4 /* +1 if page crossed */, Absolute_Y),
+      Opcode::new(0x41, EOR, 2, 6, Indirect_X),
+      Opcode::new(0x51, EOR, 2, 5 /* +1 if page crossed */, Indirect_Y),
+
+      Opcode::new(0x24, BIT, 2, 3, ZeroPage),
+      Opcode::new(0x2C, BIT, 3, 4, Absolute),
+
+      // arithmetic
+      Opcode::new(0x69, ADC, 2, 2, Immediate),
+      Opcode::new(0x65, ADC, 2, 3, ZeroPage),
+      Opcode::new(0x75, ADC, 2, 4, ZeroPage_X),
+      Opcode::new(0x6D, ADC, 3, 4, Absolute),
+      Opcode::new(0x7D, ADC, 3, 4 /* +1 if page crossed */, Absolute_X),
+      Opcode::new(0x79, ADC, 3, 4 /* +1 if page crossed */, Absolute_Y),
+      Opcode::new(0x61, ADC, 2, 6, Indirect_X),
+      Opcode::new(0x71, ADC, 2, 5 /* +1 if page crossed */, Indirect_Y),
+
+      Opcode::new(0xE9, SBC, 2, 2, Immediate),
+      Opcode::new(0xE5, SBC, 2, 3, ZeroPage),
+      Opcode::new(0xF5, SBC, 2, 4, ZeroPage_X),
+      Opcode::new(0xED, SBC, 3, 4, Absolute),
+      Opcode::new(0xFD, SBC, 3, 4 /* +1 if page crossed */, Absolute_X),
+      Opcode::new(0xF9, SBC, 3, 4 /* +1 if page crossed */, Absolute_Y),
+      Opcode::new(0xE1, SBC, 2, 6, Indirect_X),
+      Opcode::new(0xF1, SBC, 2, 5 /* +1 if page crossed */, Indirect_Y),
+
+      Opcode::new(0xC9, CMP, 2, 2, Immediate),
+      Opcode::new(0xC5, CMP, 2, 3, ZeroPage),
+      Opcode::new(0xD5, CMP, 2, 4, ZeroPage_X),
+      Opcode::new(0xCD, CMP, 3, 4, Absolute),
+      Opcode::new(0xDD, CMP, 3, 4 /* +1 if page crossed */, Absolute_X),
+      Opcode::new(0xD9, CMP, 3, 4 /* +1 if page crossed */, Absolute_Y),
+      Opcode::new(0xC1, CMP, 2, 6, Indirect_X),
+      Opcode::new(0xD1, CMP, 2, 5 /* +1 if page crossed */, Indirect_Y),
+
+      Opcode::new(0xE0, CPX, 2, 2, Immediate),
+      Opcode::new(0xE4, CPX, 2, 3, ZeroPage),
+      Opcode::new(0xEC, CPX, 3, 4, Absolute),
+
+      Opcode::new(0xC0, CPY, 2, 2, Immediate),
+      Opcode::new(0xC4, CPY, 2, 3, ZeroPage),
+      Opcode::new(0xCC, CPY, 3, 4, Absolute),
+
+      // inc / dec
+      Opcode::new(0xE6, INC, 2, 5, ZeroPage),
+      Opcode::new(0xF6, INC, 2, 6, ZeroPage_X),
+      Opcode::new(0xEE, INC, 3, 6, Absolute),
+      Opcode::new(0xFE, INC, 3, 7, Absolute_X),
+
+      Opcode::new(0xC6, DEC, 2, 5, ZeroPage),
+      Opcode::new(0xD6, DEC, 2, 6, ZeroPage_X),
+      Opcode::new(0xCE, DEC, 3, 6, Absolute),
+      Opcode::new(0xDE, DEC, 3, 7, Absolute_X),
+
+      Opcode::new(0xE8, INX, 1, 2, Implied),
+      Opcode::new(0xC8, INY, 1, 2, Implied),
+      Opcode::new(0xCA, DEX, 1, 2, Implied),
+      Opcode::new(0x88, DEY, 1, 2, Implied),
+
+      // shifts / rotates
+      Opcode::new(0x0A, ASL, 1, 2, Accumulator),
+      Opcode::new(0x06, ASL, 2, 5, ZeroPage),
+      Opcode::new(0x16, ASL, 2, 6, ZeroPage_X),
+      Opcode::new(0x0E, ASL, 3, 6, Absolute),
+      Opcode::new(0x1E, ASL, 3, 7, Absolute_X),
+
+      Opcode::new(0x4A, LSR, 1, 2, Accumulator),
+      Opcode::new(0x46, LSR, 2, 5, ZeroPage),
+      Opcode::new(0x56, LSR, 2, 6, ZeroPage_X),
+      Opcode::new(0x4E, LSR, 3, 6, Absolute),
+      Opcode::new(0x5E, LSR, 3, 7, Absolute_X),
+
+      Opcode::new(0x2A, ROL, 1, 2, Accumulator),
+      Opcode::new(0x26, ROL, 2, 5, ZeroPage),
+      Opcode::new(0x36, ROL, 2, 6, ZeroPage_X),
+      Opcode::new(0x2E, ROL, 3, 6, Absolute),
+      Opcode::new(0x3E, ROL, 3, 7, Absolute_X),
+
+      Opcode::new(0x6A, ROR, 1, 2, Accumulator),
+      Opcode::new(0x66, ROR, 2, 5, ZeroPage),
+      Opcode::new(0x76, ROR, 2, 6, ZeroPage_X),
+      Opcode::new(0x6E, ROR, 3, 6, Absolute),
+      Opcode::new(0x7E, ROR, 3, 7, Absolute_X),
+
+      // flag ops
+      Opcode::new(0x18, CLC, 1, 2, Implied),
+      Opcode::new(0x38, SEC, 1, 2, Implied),
+      Opcode::new(0x58, CLI, 1, 2, Implied),
+      Opcode::new(0x78, SEI, 1, 2, Implied),
+      Opcode::new(0xD8, CLD, 1, 2, Implied),
+      Opcode::new(0xF8, SED, 1, 2, Implied),
+      Opcode::new(0xB8, CLV, 1, 2, Implied),
+
+      // branches
+      Opcode::new(0x90, BCC, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+      Opcode::new(0xB0, BCS, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+      Opcode::new(0xF0, BEQ, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+      Opcode::new(0xD0, BNE, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+      Opcode::new(0x30, BMI, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+      Opcode::new(0x10, BPL, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+      Opcode::new(0x50, BVC, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+      Opcode::new(0x70, BVS, 2, 2 /* +1 taken, +1 page crossed */, Relative),
+
+      // jumps / calls
+      Opcode::new(0x4C, JMP, 3, 3, Absolute),
+      Opcode::new(0x6C, JMP, 3, 5, Indirect),
+      Opcode::new(0x20, JSR, 3, 6, Absolute),
+      Opcode::new(0x60, RTS, 1, 6, Implied),
+
+      // stack
+      Opcode::new(0x48, PHA, 1, 3, Implied),
+      Opcode::new(0x68, PLA, 1, 4, Implied),
+      Opcode::new(0x08, PHP, 1, 3, Implied),
+      Opcode::new(0x28, PLP, 1, 4, Implied),
+
+      // system
+      Opcode::new(0x40, RTI, 1, 6, Implied),
    ];
 
    pub static ref OPCODES_MAP: HashMap<u8, &'static Opcode> = {
@@ -74,4 +256,4 @@ lazy_static! {
          .for_each(|opcode| { map.insert(opcode.hex, opcode); });
       map
    };
-}
\ No newline at end of file
+}
@@ -0,0 +1,259 @@
+use std::ops::RangeInclusive;
+
+use crate::bus::Bus;
+
+/// A device that can intercept bus accesses within a registered address
+/// range, servicing reads and writes itself instead of whatever backing
+/// storage normally lives there.
+pub trait Peripheral {
+    /// Attempts to service a read at `addr`. `None` means this peripheral
+    /// doesn't claim the address, so the backing bus should handle it.
+    fn read(&self, addr: u16) -> Option<u8>;
+
+    /// Attempts to service a write at `addr`. Returns `true` if this
+    /// peripheral claimed the write — whether by storing it, triggering a
+    /// side effect, or silently dropping it (e.g. a write-inhibited ROM
+    /// window) — so the backing bus should not also see it.
+    fn write(&mut self, addr: u16, data: u8) -> bool;
+}
+
+/// A [`Bus`] decorated with registered [`Peripheral`]s: devices mapped over
+/// address ranges that get first refusal on reads and writes before they
+/// fall through to the backing bus, the way memory-mapped I/O registers and
+/// bank-switched cartridge windows work on real 8-bit hardware.
+///
+/// Peripheral state isn't included in [`Bus::snapshot`]/[`Bus::restore`] —
+/// only the backing bus is captured. A save-state taken with banks switched
+/// away from their defaults won't restore that selection.
+pub struct PeripheralBus {
+    backing: Box<dyn Bus>,
+    peripherals: Vec<(RangeInclusive<u16>, Box<dyn Peripheral>)>,
+}
+
+impl PeripheralBus {
+    pub fn new(backing: Box<dyn Bus>) -> Self {
+        PeripheralBus {
+            backing,
+            peripherals: Vec::new(),
+        }
+    }
+
+    /// Maps `peripheral` over `range`. Later registrations take priority
+    /// over earlier ones when ranges overlap.
+    pub fn register(&mut self, range: RangeInclusive<u16>, peripheral: Box<dyn Peripheral>) {
+        self.peripherals.push((range, peripheral));
+    }
+}
+
+impl Bus for PeripheralBus {
+    fn read(&self, addr: u16) -> u8 {
+        for (range, peripheral) in self.peripherals.iter().rev() {
+            if range.contains(&addr) {
+                if let Some(data) = peripheral.read(addr) {
+                    return data;
+                }
+            }
+        }
+        self.backing.read(addr)
+    }
+
+    fn write(&mut self, addr: u16, data: u8) {
+        for (range, peripheral) in self.peripherals.iter_mut().rev() {
+            if range.contains(&addr) && peripheral.write(addr, data) {
+                return;
+            }
+        }
+        self.backing.write(addr, data);
+    }
+
+    fn snapshot(&self) -> Vec<u8> {
+        self.backing.snapshot()
+    }
+
+    fn restore(&mut self, data: &[u8]) {
+        self.backing.restore(data);
+    }
+}
+
+/// A bank-switched address window: a control register selects which bank
+/// services reads and which services writes, independently, and can mark
+/// the window write-inhibited so writes are dropped instead of landing
+/// anywhere. This is the classic cartridge trick of mapping ROM and
+/// battery-backed RAM into the same CPU-visible window — the same address
+/// reads ROM while writes go to the RAM underneath it.
+///
+/// A control-register write's bits select state:
+/// - bits 0-3: read bank index (wrapped into range by the bank count)
+/// - bits 4-6: write bank index (wrapped into range by the bank count)
+/// - bit 7: write-inhibit (`1` drops writes in the window instead of
+///   reaching the selected write bank)
+///
+/// If `control_register` falls outside the data window, the range passed to
+/// [`PeripheralBus::register`] must cover it too, or its writes will never
+/// reach this device.
+pub struct BankSwitchedWindow {
+    window_start: u16,
+    control_register: u16,
+    read_banks: Vec<Vec<u8>>,
+    write_banks: Vec<Vec<u8>>,
+    read_bank: usize,
+    write_bank: usize,
+    write_inhibited: bool,
+}
+
+impl BankSwitchedWindow {
+    /// `window_start` is the first address of the window, sized to the
+    /// banks' (equal) length. `control_register` is the address that
+    /// selects banks and write-inhibit when written; it need not fall
+    /// inside the window itself.
+    pub fn new(
+        window_start: u16,
+        control_register: u16,
+        read_banks: Vec<Vec<u8>>,
+        write_banks: Vec<Vec<u8>>,
+    ) -> Self {
+        assert!(!read_banks.is_empty(), "must have at least one read bank");
+        assert!(!write_banks.is_empty(), "must have at least one write bank");
+        let window_len = read_banks[0].len();
+        assert!(
+            read_banks.iter().chain(write_banks.iter()).all(|bank| bank.len() == window_len),
+            "all read and write banks must share the same length"
+        );
+        BankSwitchedWindow {
+            window_start,
+            control_register,
+            read_banks,
+            write_banks,
+            read_bank: 0,
+            write_bank: 0,
+            write_inhibited: false,
+        }
+    }
+}
+
+impl Peripheral for BankSwitchedWindow {
+    fn read(&self, addr: u16) -> Option<u8> {
+        let bank = &self.read_banks[self.read_bank];
+        let offset = addr.checked_sub(self.window_start)? as usize;
+        bank.get(offset).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> bool {
+        if addr == self.control_register {
+            self.read_bank = (data & 0x0F) as usize % self.read_banks.len();
+            self.write_bank = ((data >> 4) & 0x07) as usize % self.write_banks.len();
+            self.write_inhibited = data & 0x80 != 0;
+            return true;
+        }
+
+        let Some(offset) = addr.checked_sub(self.window_start).map(|o| o as usize) else {
+            return false;
+        };
+        let Some(byte) = self.write_banks[self.write_bank].get_mut(offset) else {
+            return false;
+        };
+        if !self.write_inhibited {
+            *byte = data;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bus::FlatMemory;
+
+    struct ConstantRegister(u8);
+
+    impl Peripheral for ConstantRegister {
+        fn read(&self, _addr: u16) -> Option<u8> {
+            Some(self.0)
+        }
+
+        fn write(&mut self, _addr: u16, _data: u8) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn unclaimed_addresses_fall_through_to_the_backing_bus() {
+        let mut bus = PeripheralBus::new(Box::new(FlatMemory::new()));
+        bus.register(0x2000..=0x2000, Box::new(ConstantRegister(0x42)));
+
+        bus.write(0x0000, 0x11);
+        assert_eq!(bus.read(0x0000), 0x11);
+    }
+
+    #[test]
+    fn a_registered_peripheral_intercepts_reads_and_writes_in_its_range() {
+        let mut bus = PeripheralBus::new(Box::new(FlatMemory::new()));
+        bus.register(0x2000..=0x2000, Box::new(ConstantRegister(0x42)));
+
+        assert_eq!(bus.read(0x2000), 0x42);
+        bus.write(0x2000, 0xFF);
+        // The write is claimed (side effect happened inside the device, if
+        // any), so it must not also land in backing memory.
+        assert_eq!(bus.backing.read(0x2000), 0x00);
+    }
+
+    #[test]
+    fn later_registrations_take_priority_on_overlapping_ranges() {
+        let mut bus = PeripheralBus::new(Box::new(FlatMemory::new()));
+        bus.register(0x2000..=0x2FFF, Box::new(ConstantRegister(0x11)));
+        bus.register(0x2000..=0x2FFF, Box::new(ConstantRegister(0x22)));
+
+        assert_eq!(bus.read(0x2000), 0x22);
+    }
+
+    #[test]
+    fn bank_switched_window_reads_rom_while_writes_land_in_ram() {
+        let read_banks = vec![vec![0xAA; 0x10], vec![0xBB; 0x10]];
+        let write_banks = vec![vec![0x00; 0x10]];
+        let mut bus = PeripheralBus::new(Box::new(FlatMemory::new()));
+        bus.register(
+            0x8000..=0x800F,
+            Box::new(BankSwitchedWindow::new(0x8000, 0x9000, read_banks, write_banks)),
+        );
+
+        assert_eq!(bus.read(0x8000), 0xAA);
+        bus.write(0x8000, 0x55);
+        // The write landed in the (separate) write bank, not the read bank,
+        // so the same address still reads the ROM byte afterward.
+        assert_eq!(bus.read(0x8000), 0xAA);
+    }
+
+    #[test]
+    fn control_register_switches_the_read_bank() {
+        let read_banks = vec![vec![0xAA; 0x10], vec![0xBB; 0x10]];
+        let write_banks = vec![vec![0x00; 0x10]];
+        let mut bus = PeripheralBus::new(Box::new(FlatMemory::new()));
+        // The control register at 0x9000 lives outside the 0x8000..=0x800F
+        // window itself, so it must be registered too for its write to
+        // reach the device rather than falling through to backing memory.
+        bus.register(
+            0x8000..=0x9000,
+            Box::new(BankSwitchedWindow::new(0x8000, 0x9000, read_banks, write_banks)),
+        );
+
+        bus.write(0x9000, 0x01);
+        assert_eq!(bus.read(0x8000), 0xBB);
+    }
+
+    #[test]
+    fn write_inhibit_drops_writes_instead_of_reaching_the_write_bank() {
+        let read_banks = vec![vec![0xAA; 0x10]];
+        let write_banks = vec![vec![0x00; 0x10]];
+        let mut window = BankSwitchedWindow::new(0x8000, 0x9000, read_banks, write_banks);
+
+        // Set the write-inhibit bit (0x80), keeping write bank 0 selected.
+        assert!(window.write(0x9000, 0x80));
+        assert!(window.write(0x8000, 0x99));
+        assert_eq!(window.write_banks[0][0], 0x00, "write-inhibited write must not reach the bank");
+
+        // Clear write-inhibit and confirm the same write now lands.
+        assert!(window.write(0x9000, 0x00));
+        assert!(window.write(0x8000, 0x99));
+        assert_eq!(window.write_banks[0][0], 0x99);
+    }
+}
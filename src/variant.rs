@@ -0,0 +1,94 @@
+use crate::cpu::AddressingMode;
+use crate::opcode::{CpuMnemonic, OPCODES_MAP};
+
+/// Decides which opcodes a [`crate::cpu::CPU`] understands and whether it
+/// honors decimal-mode arithmetic, so one core can emulate several distinct
+/// 65xx chip revisions instead of hard-coding the NMOS 6502 map.
+pub trait Variant {
+    /// Looks up `hex` in this variant's opcode map, returning its mnemonic,
+    /// addressing mode, and base cycle cost, or `None` if this variant
+    /// leaves the opcode undefined.
+    fn decode(&self, hex: u8) -> Option<(CpuMnemonic, AddressingMode, u8)>;
+
+    /// Whether `ADC`/`SBC` honor the decimal flag. The Ricoh 2A03 used in
+    /// the NES had the BCD circuitry omitted, so it returns `false` even
+    /// with `D` set.
+    fn supports_decimal_mode(&self) -> bool {
+        true
+    }
+}
+
+/// Shared lookup into [`OPCODES_MAP`], the full legal NMOS map every
+/// variant in this file derives from.
+fn lookup(hex: u8) -> Option<(CpuMnemonic, AddressingMode, u8)> {
+    OPCODES_MAP
+        .get(&hex)
+        .map(|opcode| (opcode.mnemonic, opcode.addressing, opcode.cycles))
+}
+
+/// The standard NMOS 6502 instruction set documented in
+/// [`crate::opcode::CPU_OPS_CODES`].
+pub struct Nmos6502;
+
+impl Variant for Nmos6502 {
+    fn decode(&self, hex: u8) -> Option<(CpuMnemonic, AddressingMode, u8)> {
+        lookup(hex)
+    }
+}
+
+/// The earliest 6502 revision, shipped before `ROR` was wired up. Leaving
+/// its opcodes undefined here surfaces the gap as an `IllegalOpcode` error
+/// rather than silently emulating the later chip.
+pub struct RevisionA;
+
+impl Variant for RevisionA {
+    fn decode(&self, hex: u8) -> Option<(CpuMnemonic, AddressingMode, u8)> {
+        match lookup(hex)? {
+            (CpuMnemonic::ROR, ..) => None,
+            decoded => Some(decoded),
+        }
+    }
+}
+
+/// The Ricoh 2A03 used in the NES: the standard NMOS opcode map, but with
+/// the BCD circuitry omitted, so `ADC`/`SBC` always run in binary mode
+/// regardless of the decimal flag.
+pub struct Ricoh2A03;
+
+impl Variant for Ricoh2A03 {
+    fn decode(&self, hex: u8) -> Option<(CpuMnemonic, AddressingMode, u8)> {
+        lookup(hex)
+    }
+
+    fn supports_decimal_mode(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nmos6502_decodes_ror() {
+        let (mnemonic, ..) = Nmos6502.decode(0x6A).unwrap();
+        assert_eq!(mnemonic, CpuMnemonic::ROR);
+    }
+
+    #[test]
+    fn revision_a_leaves_ror_undefined() {
+        assert!(RevisionA.decode(0x6A).is_none());
+    }
+
+    #[test]
+    fn revision_a_still_decodes_everything_else() {
+        let (mnemonic, ..) = RevisionA.decode(0xA9).unwrap();
+        assert_eq!(mnemonic, CpuMnemonic::LDA);
+    }
+
+    #[test]
+    fn ricoh_2a03_ignores_decimal_mode() {
+        assert!(!Ricoh2A03.supports_decimal_mode());
+        assert!(Nmos6502.supports_decimal_mode());
+    }
+}
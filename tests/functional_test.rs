@@ -0,0 +1,64 @@
+//! Runs Klaus Dormann's 6502 functional test suite against the full
+//! decode/execute path — far more coverage than the unit tests in `src/`
+//! exercise on their own. This is the standard correctness proof used by
+//! 6502 cores generally.
+//!
+//! The ROM binary isn't vendored in this repo (it's tens of kilobytes and
+//! carries its own license). To run it:
+//!
+//! 1. Build `6502_functional_test.bin` from
+//!    <https://github.com/Klaus2m5/6502_65C02_functional_tests>.
+//! 2. Place it at `tests/fixtures/6502_functional_test.bin`.
+//! 3. `cargo test --test functional_test -- --ignored`
+//!
+//! The test is `#[ignore]`d so a plain `cargo test` never needs the ROM.
+
+use snes_emu::bus::{Bus, FlatMemory};
+use snes_emu::cpu::CPU;
+use snes_emu::variant::Nmos6502;
+
+/// Where the test binary expects to be loaded.
+const LOAD_ADDRESS: u16 = 0x0000;
+/// Entry point once `LOAD_ADDRESS + 0x0400` is reached, per the test's own
+/// assembly listing.
+const START_ADDRESS: u16 = 0x0400;
+/// The infinite loop the program traps to on success; any other trap
+/// (the PC no longer advancing) is a failing test case.
+const SUCCESS_TRAP: u16 = 0x3469;
+
+#[test]
+#[ignore]
+fn klaus_dormann_functional_test() {
+    let rom_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/6502_functional_test.bin"
+    );
+    let rom = std::fs::read(rom_path).unwrap_or_else(|e| {
+        panic!(
+            "couldn't read {rom_path}: {e} (see the doc comment at the top \
+             of this file for how to provide the ROM)"
+        )
+    });
+
+    let mut memory = FlatMemory::new();
+    for (offset, byte) in rom.iter().enumerate() {
+        memory.write(LOAD_ADDRESS.wrapping_add(offset as u16), *byte);
+    }
+
+    let mut cpu = CPU::new(Box::new(memory), Box::new(Nmos6502));
+    cpu.program_counter = START_ADDRESS;
+
+    loop {
+        let pc_before = cpu.program_counter;
+        cpu.step().expect("illegal opcode while running the functional test ROM");
+        let pc_after = cpu.program_counter;
+
+        if pc_after == SUCCESS_TRAP {
+            return;
+        }
+        assert_ne!(
+            pc_after, pc_before,
+            "stuck at ${pc_after:04X} (trap, not the success address ${SUCCESS_TRAP:04X})"
+        );
+    }
+}